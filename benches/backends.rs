@@ -0,0 +1,97 @@
+use std::hint::black_box;
+use std::sync::Mutex;
+use std::thread;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+
+use libcangjie_howtotype::{CangjieDatabase, CangjieFilter, CangjieVersion, LibCangjieHowToType};
+
+/// The number of characters queried in each iteration.
+const CHARACTERS: usize = 3000;
+
+/// The number of threads querying at once in the `shared` group.
+const THREADS: usize = 4;
+
+/// Returns up to [`CHARACTERS`] characters in the database.
+fn characters(cangjie: &LibCangjieHowToType) -> Vec<String> {
+    let mut characters = cangjie
+        .database()
+        .entries(CangjieVersion::V3, CangjieFilter::empty())
+        .expect("Cannot list the characters")
+        .into_iter()
+        .map(|(character, _)| character)
+        .collect::<Vec<_>>();
+    characters.dedup();
+    characters.truncate(CHARACTERS);
+    characters
+}
+
+fn how_to_type(c: &mut Criterion) {
+    let sqlite = LibCangjieHowToType::new().expect("Cannot open libcangjie's database");
+    let in_memory = sqlite
+        .to_in_memory()
+        .expect("Cannot load libcangjie's database");
+    let characters = characters(&sqlite);
+
+    let mut group = c.benchmark_group("how_to_type");
+    group.bench_function("sqlite", |b| {
+        b.iter(|| {
+            for character in &characters {
+                black_box(sqlite.how_to_type(character, CangjieVersion::V3).unwrap());
+            }
+        });
+    });
+    group.bench_function("in_memory", |b| {
+        b.iter(|| {
+            for character in &characters {
+                black_box(
+                    in_memory
+                        .how_to_type(character, CangjieVersion::V3)
+                        .unwrap(),
+                );
+            }
+        });
+    });
+    group.finish();
+}
+
+fn shared(c: &mut Criterion) {
+    let sqlite = LibCangjieHowToType::new().expect("Cannot open libcangjie's database");
+    let characters = characters(&sqlite);
+    let mutex = Mutex::new(sqlite);
+    let shared = LibCangjieHowToType::new_shared().expect("Cannot open libcangjie's database");
+
+    // Each thread queries all the characters.
+    let mut group = c.benchmark_group("shared");
+    group.bench_function("mutex", |b| {
+        b.iter(|| {
+            thread::scope(|s| {
+                for _ in 0..THREADS {
+                    s.spawn(|| {
+                        for character in &characters {
+                            let cangjie = mutex.lock().unwrap();
+                            black_box(cangjie.how_to_type(character, CangjieVersion::V3).unwrap());
+                        }
+                    });
+                }
+            });
+        });
+    });
+    group.bench_function("shared", |b| {
+        b.iter(|| {
+            thread::scope(|s| {
+                for _ in 0..THREADS {
+                    s.spawn(|| {
+                        for character in &characters {
+                            black_box(shared.how_to_type(character, CangjieVersion::V3).unwrap());
+                        }
+                    });
+                }
+            });
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, how_to_type, shared);
+criterion_main!(benches);
@@ -0,0 +1,53 @@
+//! Generates the data embedded with the `embedded-data` feature.
+//!
+//! The database is read from `LIBCANGJIE_DB` if set,
+//! or from `/usr/share/libcangjie/cangjie.db` otherwise.
+//! See `src/embedded.rs` for the tables generated.
+
+#[cfg(feature = "embedded-data")]
+#[path = "src/schema.rs"]
+mod schema;
+
+fn main() {
+    println!("cargo::rerun-if-changed=build.rs");
+
+    #[cfg(feature = "embedded-data")]
+    embedded::generate();
+}
+
+#[cfg(feature = "embedded-data")]
+mod embedded {
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::schema::{DB_VERSION_V3, DB_VERSION_V5, EXPECTED_COLUMNS};
+
+    /// The environment variable that explicitly specifies the database path.
+    const DB_PATH_ENV: &str = "LIBCANGJIE_DB";
+
+    /// The database used if `LIBCANGJIE_DB` is not set.
+    const DEFAULT_DB_PATH: &str = "/usr/share/libcangjie/cangjie.db";
+
+    pub fn generate() {
+        println!("cargo::rerun-if-changed=src/schema.rs");
+        println!("cargo::rerun-if-env-changed={DB_PATH_ENV}");
+        let path = env::var_os(DB_PATH_ENV)
+            .filter(|path| !path.is_empty())
+            .map_or_else(|| PathBuf::from(DEFAULT_DB_PATH), PathBuf::from);
+        println!("cargo::rerun-if-changed={}", path.display());
+
+        let versions = [("V3", DB_VERSION_V3), ("V5", DB_VERSION_V5)];
+        let tables = libcangjie_howtotype_build::tables(&path, &EXPECTED_COLUMNS, &versions)
+            .unwrap_or_else(|e| {
+                panic!(
+                    "Cannot embed libcangjie's database {}: {e}\n\
+                     Set {DB_PATH_ENV} to specify its location",
+                    path.display(),
+                )
+            });
+
+        let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR is not set"));
+        fs::write(out_dir.join("embedded.rs"), tables).expect("Cannot write the embedded data");
+    }
+}
@@ -0,0 +1,219 @@
+//! Build-time support for the `embedded-data` feature of libcangjie-howtotype.
+//!
+//! This reads libcangjie's database and generates the static tables
+//! embedded by `libcangjie-howtotype`; see its `src/embedded.rs`.
+//! It is a separate crate so that rusqlite is only a build dependency there,
+//! as Cargo cannot enable a build dependency and an optional normal dependency
+//! on the same crate separately.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Write;
+use std::path::Path;
+
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags, Row};
+
+/// The flag columns of `chars`, in the bit order of `CangjieFilter`.
+const FLAG_COLUMNS: [&str; 9] = [
+    "big5", "hkscs", "punct", "zh", "zhuyin", "kanji", "katakana", "hiragana", "symbol",
+];
+
+/// A character read from `chars`.
+struct Character {
+    char_index: i64,
+    character: String,
+    simplified: Option<String>,
+    flags: u32,
+    frequency: u32,
+}
+
+/// Generates the Rust source of the tables from the database at `path`.
+///
+/// `expected_columns` lists the tables and columns read,
+/// and `versions` the name of each table of codes with its `codes.version`.
+///
+/// # Errors
+///
+/// Returns an error if the database cannot be read,
+/// lacks some of the expected columns, or contains unexpected data.
+pub fn tables(
+    path: &Path,
+    expected_columns: &[(&str, &[&str])],
+    versions: &[(&str, u32)],
+) -> Result<String, Box<dyn Error>> {
+    let db_conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    check_schema(&db_conn, expected_columns)?;
+
+    let characters = characters(&db_conn)?;
+    let positions = characters
+        .iter()
+        .enumerate()
+        .map(|(position, character)| (character.char_index, position))
+        .collect::<HashMap<_, _>>();
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "pub(super) static CHARACTERS: [Character; {}] = [",
+        characters.len(),
+    )?;
+    for character in &characters {
+        writeln!(
+            out,
+            "    Character {{ character: {:?}, simplified: {:?}, \
+             flags: CangjieFilter::from_bits_retain({}), frequency: {} }},",
+            character.character, character.simplified, character.flags, character.frequency,
+        )?;
+    }
+    writeln!(out, "];")?;
+
+    let mut by_character = (0..characters.len()).collect::<Vec<_>>();
+    by_character.sort_unstable_by_key(|&position| &characters[position].character);
+    writeln!(
+        out,
+        "pub(super) static BY_CHARACTER: [u32; {}] = {by_character:?};",
+        by_character.len(),
+    )?;
+
+    for &(name, version) in versions {
+        let mut entries = codes(&db_conn, version)?
+            .into_iter()
+            .filter_map(|(char_index, code)| {
+                positions.get(&char_index).map(|&position| (position, code))
+            })
+            .collect::<Vec<_>>();
+        entries.sort_unstable();
+
+        let mut by_code = (0..entries.len()).collect::<Vec<_>>();
+        by_code.sort_unstable_by_key(|&index| (&entries[index].1, entries[index].0));
+
+        writeln!(out, "pub(super) static {name}: CodeTable = CodeTable {{")?;
+        writeln!(out, "    entries: &[")?;
+        for (position, code) in &entries {
+            let radicals = code
+                .chars()
+                .map(|code| code.to_ascii_uppercase().to_string())
+                .collect::<Vec<_>>();
+            writeln!(out, "        ({position}, &[{}]),", radicals.join(", "))?;
+        }
+        writeln!(out, "    ],")?;
+        writeln!(out, "    by_code: &{by_code:?},")?;
+        writeln!(out, "}};")?;
+    }
+
+    Ok(out)
+}
+
+/// Checks that the database has the tables and columns read.
+fn check_schema(
+    db_conn: &Connection,
+    expected_columns: &[(&str, &[&str])],
+) -> Result<(), Box<dyn Error>> {
+    let mut missing = Vec::new();
+    for (table, columns) in expected_columns {
+        let mut stmt = db_conn.prepare("SELECT name FROM pragma_table_info(?1)")?;
+        let present = stmt
+            .query_map((table,), |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        missing.extend(
+            columns
+                .iter()
+                .filter(|column| !present.iter().any(|present| present == *column))
+                .map(|column| format!("{table}.{column}")),
+        );
+    }
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Incompatible database schema, missing: {}",
+            missing.join(", ")
+        )
+        .into())
+    }
+}
+
+/// Reads all characters, in candidate order.
+fn characters(db_conn: &Connection) -> Result<Vec<Character>, Box<dyn Error>> {
+    let mut stmt = db_conn.prepare(&format!(
+        r"
+            SELECT char_index, chchar, simpchar, frequency, {}
+            FROM chars
+            ORDER BY frequency DESC, char_index
+        ",
+        FLAG_COLUMNS.join(", "),
+    ))?;
+    let mut rows = stmt.query(())?;
+
+    let mut characters = Vec::new();
+    while let Some(row) = rows.next()? {
+        let char_index = row.get(0)?;
+        let character = text(row, 1, char_index, "chars.chchar")?;
+        let simplified = match row.get_ref(2)? {
+            ValueRef::Null => None,
+            _ => Some(text(row, 2, char_index, "chars.simpchar")?)
+                .filter(|simplified| !simplified.is_empty()),
+        };
+        let frequency = match row.get_ref(3)? {
+            ValueRef::Null => 0,
+            ValueRef::Integer(frequency) => u32::try_from(frequency)?,
+            _ => return Err(unexpected(char_index, "chars.frequency")),
+        };
+        let mut flags = 0;
+        for (bit, column) in FLAG_COLUMNS.iter().enumerate() {
+            match row.get_ref(4 + bit)? {
+                ValueRef::Null | ValueRef::Integer(0) => {}
+                ValueRef::Integer(_) => flags |= 1 << bit,
+                _ => return Err(unexpected(char_index, &format!("chars.{column}"))),
+            }
+        }
+
+        characters.push(Character {
+            char_index,
+            character,
+            simplified,
+            flags,
+            frequency,
+        });
+    }
+
+    Ok(characters)
+}
+
+/// Reads all codes of a version, as `(char_index, code)` pairs.
+fn codes(db_conn: &Connection, version: u32) -> Result<Vec<(i64, String)>, Box<dyn Error>> {
+    let mut stmt = db_conn.prepare("SELECT char_index, code FROM codes WHERE version = ?1")?;
+    let mut rows = stmt.query((version,))?;
+
+    let mut codes = Vec::new();
+    while let Some(row) = rows.next()? {
+        let char_index = row.get(0)?;
+        let code = text(row, 1, char_index, "codes.code")?;
+        if code.is_empty() || !code.bytes().all(|code| code.is_ascii_lowercase()) {
+            return Err(format!("Invalid code {code:?} for character {char_index}").into());
+        }
+
+        codes.push((char_index, code));
+    }
+
+    Ok(codes)
+}
+
+/// Reads a text value, checking its type.
+fn text(
+    row: &Row<'_>,
+    index: usize,
+    char_index: i64,
+    column: &str,
+) -> Result<String, Box<dyn Error>> {
+    match row.get_ref(index)? {
+        ValueRef::Text(text) => Ok(String::from_utf8(text.to_vec())?),
+        _ => Err(unexpected(char_index, column)),
+    }
+}
+
+fn unexpected(char_index: i64, column: &str) -> Box<dyn Error> {
+    format!("Unexpected value type in {column} for character {char_index}").into()
+}
@@ -0,0 +1,380 @@
+//! The async API, with the `async` feature.
+
+use std::num::NonZeroUsize;
+use std::panic;
+use std::sync::Arc;
+
+use smallvec::SmallVec;
+use tokio::sync::Semaphore;
+use tokio::task;
+
+use crate::{
+    CandidateCode, CangjieCode, CangjieDatabase, CangjieFilter, CangjieVersion, CharacterInfo,
+    CodePattern, HowToTypeError, HowToTypeResult, LibCangjieHowToType, QuickCode,
+};
+#[cfg(feature = "sqlite")]
+use crate::{NewError, NewResult, SqlitePool};
+
+/// The async counterpart of [`LibCangjieHowToType`], for use with Tokio.
+///
+/// Each query runs on Tokio's blocking thread pool with
+/// [`spawn_blocking`](tokio::task::spawn_blocking),
+/// so that the database never blocks the async worker threads.
+/// The backend must thus be `Send + Sync`,
+/// such as [`SqlitePool`](crate::SqlitePool) (the default with the `sqlite` feature),
+/// [`InMemoryCangjie`](crate::InMemoryCangjie) or `EmbeddedCangjie`.
+/// Without the `sqlite` feature there is no default backend,
+/// so it is written e.g. `AsyncCangjie<EmbeddedCangjie>`.
+///
+/// At most [`DEFAULT_MAX_CONCURRENT_QUERIES`](Self::DEFAULT_MAX_CONCURRENT_QUERIES)
+/// queries run at once, the others waiting for their turn,
+/// so that a burst of queries neither fills Tokio's blocking pool
+/// nor opens more connections than [`SqlitePool`](crate::SqlitePool) keeps idle.
+/// See [`with_max_concurrent_queries`](Self::with_max_concurrent_queries).
+///
+/// `AsyncCangjie` is cheap to clone, sharing the same backend and limit.
+///
+/// # Cancellation safety
+///
+/// All query methods are cancellation safe.
+/// If a query future is dropped before it completes,
+/// the query still runs to completion on the blocking pool,
+/// its result is discarded, and the backend is left in a consistent state.
+///
+/// # Panics
+///
+/// The query methods must be called within a Tokio runtime.
+/// A panic in a query is propagated to the awaiting task.
+/// If the runtime is shutting down,
+/// the query methods return [`HowToTypeError::RuntimeShutdown`] instead.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # use libcangjie_howtotype::{AsyncCangjie, CangjieCode, CangjieVersion};
+/// #
+/// # #[cfg(feature = "sqlite")]
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn Error>> {
+/// let cangjie = AsyncCangjie::new().await?;
+///
+/// let how_to_type = cangjie.how_to_type("喵", CangjieVersion::V3).await?;
+/// assert_eq!(*how_to_type, [CangjieCode::from_radicals("口廿田")]);
+/// #
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "sqlite"))]
+/// # fn main() {}
+/// ```
+#[derive(Debug)]
+pub struct AsyncCangjie<
+    #[cfg(feature = "sqlite")] D = SqlitePool,
+    #[cfg(not(feature = "sqlite"))] D,
+> {
+    cangjie: Arc<LibCangjieHowToType<D>>,
+    permits: Arc<Semaphore>,
+    max_concurrent_queries: usize,
+}
+
+#[cfg(feature = "sqlite")]
+impl AsyncCangjie {
+    /// Creates a new `AsyncCangjie`
+    /// using the first database found in the search path.
+    ///
+    /// The database is opened on the blocking pool as well.
+    /// See [`LibCangjieHowToType::new_shared`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`LibCangjieHowToType::new`],
+    /// or [`NewError::RuntimeShutdown`] if the Tokio runtime is shutting down.
+    pub async fn new() -> NewResult<Self> {
+        spawn(LibCangjieHowToType::new_shared)
+            .await
+            .unwrap_or(Err(NewError::RuntimeShutdown))
+            .map(Self::from)
+    }
+}
+
+impl<D> AsyncCangjie<D> {
+    /// The default maximum number of queries running at once,
+    /// matching the idle connections kept by [`SqlitePool`](crate::SqlitePool).
+    pub const DEFAULT_MAX_CONCURRENT_QUERIES: usize = 16;
+
+    /// Creates a new `AsyncCangjie` with the given backend.
+    #[must_use]
+    pub fn from_database(database: D) -> Self {
+        Self::from(LibCangjieHowToType::from_database(database))
+    }
+
+    /// Sets the maximum number of queries running at once.
+    ///
+    /// The limit is shared with the clones made afterwards,
+    /// but not with those made before.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::num::NonZeroUsize;
+    /// #
+    /// # use libcangjie_howtotype::AsyncCangjie;
+    /// #
+    /// # fn example<D>(database: D) {
+    /// let cangjie = AsyncCangjie::from_database(database)
+    ///     .with_max_concurrent_queries(NonZeroUsize::new(4).unwrap());
+    /// assert_eq!(cangjie.max_concurrent_queries(), 4);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_max_concurrent_queries(self, max: NonZeroUsize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(max.get())),
+            max_concurrent_queries: max.get(),
+            ..self
+        }
+    }
+
+    /// Returns the maximum number of queries running at once.
+    #[must_use]
+    pub fn max_concurrent_queries(&self) -> usize {
+        self.max_concurrent_queries
+    }
+
+    /// Returns the synchronous [`LibCangjieHowToType`], sharing the same backend.
+    #[must_use]
+    pub fn blocking(&self) -> &LibCangjieHowToType<D> {
+        &self.cangjie
+    }
+}
+
+impl<D: CangjieDatabase + Send + Sync + 'static> AsyncCangjie<D> {
+    /// Runs a query on the blocking pool, once there is room for it.
+    async fn run<T: Send + 'static>(
+        &self,
+        query: impl FnOnce(&LibCangjieHowToType<D>) -> HowToTypeResult<T> + Send + 'static,
+    ) -> HowToTypeResult<T> {
+        let permit = Arc::clone(&self.permits)
+            .acquire_owned()
+            .await
+            .expect("The semaphore is never closed");
+        let cangjie = Arc::clone(&self.cangjie);
+        // The permit is held until the query completes,
+        // even if the future awaiting it is dropped.
+        spawn(move || {
+            let result = query(&cangjie);
+            drop(permit);
+            result
+        })
+        .await
+        .unwrap_or(Err(HowToTypeError::RuntimeShutdown))
+    }
+
+    /// Queries how to type a character.
+    ///
+    /// See [`LibCangjieHowToType::how_to_type`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`LibCangjieHowToType::how_to_type`].
+    pub async fn how_to_type(
+        &self,
+        character: &str,
+        version: CangjieVersion,
+    ) -> HowToTypeResult<SmallVec<[CangjieCode; 1]>> {
+        self.how_to_type_with_filter(character, version, CangjieFilter::empty())
+            .await
+    }
+
+    /// Queries how to type a character,
+    /// only considering the characters passing a filter.
+    ///
+    /// See [`LibCangjieHowToType::how_to_type_with_filter`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`LibCangjieHowToType::how_to_type_with_filter`].
+    pub async fn how_to_type_with_filter(
+        &self,
+        character: &str,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<SmallVec<[CangjieCode; 1]>> {
+        let character = character.to_owned();
+        self.run(move |cangjie| cangjie.how_to_type_with_filter(&character, version, filter))
+            .await
+    }
+
+    /// Queries how to type a character,
+    /// together with its position in the candidate list of each code.
+    ///
+    /// See [`LibCangjieHowToType::how_to_type_with_positions`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`LibCangjieHowToType::how_to_type_with_positions`].
+    pub async fn how_to_type_with_positions(
+        &self,
+        character: &str,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<SmallVec<[CandidateCode; 1]>> {
+        let character = character.to_owned();
+        self.run(move |cangjie| cangjie.how_to_type_with_positions(&character, version, filter))
+            .await
+    }
+
+    /// Queries how to type each character in a text.
+    ///
+    /// This collects the results of [`LibCangjieHowToType::how_to_type_text`]
+    /// in a single query, stopping at the first error.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`LibCangjieHowToType::how_to_type`].
+    pub async fn how_to_type_text(
+        &self,
+        text: &str,
+        version: CangjieVersion,
+    ) -> HowToTypeResult<Vec<(String, SmallVec<[CangjieCode; 1]>)>> {
+        let text = text.to_owned();
+        self.run(move |cangjie| {
+            cangjie
+                .how_to_type_text(&text, version)
+                .map(|result| result.map(|(segment, codes)| (segment.to_owned(), codes)))
+                .collect()
+        })
+        .await
+    }
+
+    /// Queries the Quick (速成) codes of a character and its candidate positions.
+    ///
+    /// See [`LibCangjieHowToType::how_to_type_quick`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`LibCangjieHowToType::how_to_type_quick`].
+    pub async fn how_to_type_quick(
+        &self,
+        character: &str,
+    ) -> HowToTypeResult<SmallVec<[QuickCode; 1]>> {
+        let character = character.to_owned();
+        self.run(move |cangjie| cangjie.how_to_type_quick(&character))
+            .await
+    }
+
+    /// Queries the information about a character.
+    ///
+    /// See [`LibCangjieHowToType::character_info`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`LibCangjieHowToType::character_info`].
+    pub async fn character_info(&self, character: &str) -> HowToTypeResult<Option<CharacterInfo>> {
+        let character = character.to_owned();
+        self.run(move |cangjie| cangjie.character_info(&character))
+            .await
+    }
+
+    /// Queries the characters that can be typed with a code, in candidate order.
+    ///
+    /// See [`LibCangjieHowToType::characters_for`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`LibCangjieHowToType::characters_for`].
+    pub async fn characters_for(
+        &self,
+        code: &CangjieCode,
+        version: CangjieVersion,
+    ) -> HowToTypeResult<Vec<String>> {
+        self.characters_for_with_filter(code, version, CangjieFilter::empty())
+            .await
+    }
+
+    /// Queries the characters that can be typed with a code, in candidate order,
+    /// only considering the characters passing a filter.
+    ///
+    /// See [`LibCangjieHowToType::characters_for_with_filter`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`LibCangjieHowToType::characters_for_with_filter`].
+    pub async fn characters_for_with_filter(
+        &self,
+        code: &CangjieCode,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<String>> {
+        let code = code.clone();
+        self.run(move |cangjie| cangjie.characters_for_with_filter(&code, version, filter))
+            .await
+    }
+
+    /// Searches for the characters whose code matches a pattern.
+    ///
+    /// See [`LibCangjieHowToType::search`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`LibCangjieHowToType::search`].
+    pub async fn search(
+        &self,
+        pattern: &CodePattern,
+        version: CangjieVersion,
+    ) -> HowToTypeResult<Vec<(String, CangjieCode)>> {
+        self.search_with_filter(pattern, version, CangjieFilter::empty())
+            .await
+    }
+
+    /// Searches for the characters whose code matches a pattern,
+    /// only considering the characters passing a filter.
+    ///
+    /// See [`LibCangjieHowToType::search_with_filter`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`LibCangjieHowToType::search_with_filter`].
+    pub async fn search_with_filter(
+        &self,
+        pattern: &CodePattern,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<(String, CangjieCode)>> {
+        let pattern = pattern.clone();
+        self.run(move |cangjie| cangjie.search_with_filter(&pattern, version, filter))
+            .await
+    }
+}
+
+impl<D> Clone for AsyncCangjie<D> {
+    fn clone(&self) -> Self {
+        Self {
+            cangjie: Arc::clone(&self.cangjie),
+            permits: Arc::clone(&self.permits),
+            max_concurrent_queries: self.max_concurrent_queries,
+        }
+    }
+}
+
+impl<D> From<LibCangjieHowToType<D>> for AsyncCangjie<D> {
+    fn from(value: LibCangjieHowToType<D>) -> Self {
+        Self {
+            cangjie: Arc::new(value),
+            permits: Arc::new(Semaphore::new(Self::DEFAULT_MAX_CONCURRENT_QUERIES)),
+            max_concurrent_queries: Self::DEFAULT_MAX_CONCURRENT_QUERIES,
+        }
+    }
+}
+
+/// Runs a closure on the blocking pool, propagating any panic.
+///
+/// Returns `None` if the Tokio runtime is shutting down.
+async fn spawn<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> Option<T> {
+    match task::spawn_blocking(f).await {
+        Ok(result) => Some(result),
+        Err(error) if error.is_panic() => panic::resume_unwind(error.into_panic()),
+        Err(_) => None,
+    }
+}
@@ -0,0 +1,172 @@
+//! The cache of lookups in front of the backend, with the `cache` feature.
+
+use std::borrow::Borrow;
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use lru::LruCache;
+use smallvec::SmallVec;
+
+use crate::{CangjieCode, CangjieFilter, CangjieVersion, HowToTypeResult};
+
+/// The statistics of the lookup cache of a [`LibCangjieHowToType`](crate::LibCangjieHowToType).
+///
+/// See [`LibCangjieHowToType::with_cache`](crate::LibCangjieHowToType::with_cache).
+/// Without a cache, all the statistics are zero.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+#[non_exhaustive]
+pub struct CacheStats {
+    /// The number of lookups answered from the cache.
+    pub hits: u64,
+    /// The number of lookups that went to the backend.
+    pub misses: u64,
+    /// The number of lookups currently cached.
+    pub len: usize,
+    /// The maximum number of lookups cached.
+    pub capacity: usize,
+}
+
+/// The key of a lookup: the character, the version and the filter.
+#[derive(Debug)]
+struct Key {
+    character: Box<str>,
+    version: CangjieVersion,
+    filter: CangjieFilter,
+}
+
+/// A key, owned or borrowed from the arguments of a lookup,
+/// so that probing the cache does not allocate.
+trait LookupKey {
+    fn parts(&self) -> (&str, CangjieVersion, CangjieFilter);
+}
+
+impl LookupKey for Key {
+    fn parts(&self) -> (&str, CangjieVersion, CangjieFilter) {
+        (&self.character, self.version, self.filter)
+    }
+}
+
+impl LookupKey for (&str, CangjieVersion, CangjieFilter) {
+    fn parts(&self) -> (&str, CangjieVersion, CangjieFilter) {
+        *self
+    }
+}
+
+impl<'a> Borrow<dyn LookupKey + 'a> for Key {
+    fn borrow(&self) -> &(dyn LookupKey + 'a) {
+        self
+    }
+}
+
+// `Key` and `dyn LookupKey` must hash and compare alike, as required by `Borrow`.
+impl Hash for dyn LookupKey + '_ {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.parts().hash(state);
+    }
+}
+
+impl PartialEq for dyn LookupKey + '_ {
+    fn eq(&self, other: &Self) -> bool {
+        self.parts() == other.parts()
+    }
+}
+
+impl Eq for dyn LookupKey + '_ {}
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.parts().hash(state);
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.parts() == other.parts()
+    }
+}
+
+impl Eq for Key {}
+
+/// A size-bounded cache of [`CangjieDatabase::codes_for_character`](crate::CangjieDatabase::codes_for_character),
+/// evicting the least recently used lookup when full.
+#[derive(Debug)]
+pub(crate) struct LookupCache {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    codes: LruCache<Key, SmallVec<[CangjieCode; 1]>>,
+    hits: u64,
+    misses: u64,
+}
+
+impl LookupCache {
+    /// Creates an empty cache holding up to `capacity` lookups.
+    pub(crate) fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                codes: LruCache::new(capacity),
+                hits: 0,
+                misses: 0,
+            }),
+        }
+    }
+
+    /// Returns the cached codes of a character, or looks them up and caches them.
+    ///
+    /// The lock is not held during the lookup,
+    /// so that lookups from several threads still run in parallel.
+    /// Errors are not cached.
+    pub(crate) fn codes_for_character(
+        &self,
+        character: &str,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+        lookup: impl FnOnce() -> HowToTypeResult<SmallVec<[CangjieCode; 1]>>,
+    ) -> HowToTypeResult<SmallVec<[CangjieCode; 1]>> {
+        {
+            let mut inner = self.lock();
+            let key: &dyn LookupKey = &(character, version, filter);
+            if let Some(codes) = inner.codes.get(key).cloned() {
+                inner.hits += 1;
+                return Ok(codes);
+            }
+            inner.misses += 1;
+        }
+
+        let codes = lookup()?;
+        let key = Key {
+            character: Box::from(character),
+            version,
+            filter,
+        };
+        self.lock().codes.put(key, codes.clone());
+        Ok(codes)
+    }
+
+    /// Returns the statistics of the cache.
+    pub(crate) fn stats(&self) -> CacheStats {
+        let inner = self.lock();
+        CacheStats {
+            hits: inner.hits,
+            misses: inner.misses,
+            len: inner.codes.len(),
+            capacity: inner.codes.cap().get(),
+        }
+    }
+
+    /// Empties the cache and resets the statistics.
+    pub(crate) fn clear(&self) {
+        let mut inner = self.lock();
+        inner.codes.clear();
+        inner.hits = 0;
+        inner.misses = 0;
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        // The cache is consistent after every operation, so it is fine even if poisoned.
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,1407 @@
+//! Querying how to type characters, through a [`CangjieDatabase`] backend.
+
+use std::collections::HashMap;
+#[cfg(feature = "sqlite")]
+use std::env;
+#[cfg(feature = "sqlite")]
+use std::fmt::{self, Display, Formatter};
+#[cfg(feature = "cache")]
+use std::num::NonZeroUsize;
+#[cfg(feature = "sqlite")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "sqlite")]
+use std::sync::LazyLock;
+
+#[cfg(feature = "sqlite")]
+use rusqlite::Connection;
+use smallvec::SmallVec;
+use thiserror::Error;
+
+#[cfg(feature = "cache")]
+use crate::CacheStats;
+#[cfg(feature = "cache")]
+use crate::cache::LookupCache;
+#[cfg(feature = "sqlite")]
+use crate::sqlite::CipherSettings;
+use crate::{
+    CandidateCode, CangjieCode, CangjieFilter, CangjieVersion, CharacterInfo, CodePattern,
+    CodePatternElement, QuickCode,
+};
+#[cfg(feature = "sqlite")]
+use crate::{SharedCangjie, SqliteDatabase, SqlitePool};
+
+#[cfg(feature = "sqlite")]
+static DB_PATH: LazyLock<&Path> = LazyLock::new(|| Path::new("/usr/share/libcangjie/cangjie.db"));
+
+/// The environment variable that explicitly specifies the database path.
+#[cfg(feature = "sqlite")]
+const DB_PATH_ENV: &str = "LIBCANGJIE_DB";
+
+/// The path of the database relative to an XDG data directory.
+#[cfg(feature = "sqlite")]
+const DB_PATH_IN_DATA_DIR: &str = "libcangjie/cangjie.db";
+
+/// A backend providing the data of libcangjie's database.
+///
+/// [`LibCangjieHowToType`] performs all its queries through this trait,
+/// so that the data can come from somewhere other than SQLite.
+/// [`SqliteDatabase`] is the backend reading libcangjie's own database.
+///
+/// All methods take a [`CangjieFilter`],
+/// and must only return the characters passing it.
+/// Characters are expected in libcangjie's candidate order,
+/// i.e., the most frequent characters first.
+///
+/// Only [`CangjieDatabase::entries_with_flags`], [`CangjieDatabase::search`]
+/// and [`CangjieDatabase::characters_matching`]
+/// have default implementations, based on [`CangjieDatabase::entries`].
+/// Backends that can do better, such as with an index, should override them.
+pub trait CangjieDatabase {
+    /// Returns all codes of a character.
+    ///
+    /// # Errors
+    ///
+    /// Any error occurred in the backend.
+    fn codes_for_character(
+        &self,
+        character: &str,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<SmallVec<[CangjieCode; 1]>>;
+
+    /// Returns the characters that can be typed with a code, in candidate order.
+    ///
+    /// # Errors
+    ///
+    /// Any error occurred in the backend.
+    fn characters_for_code(
+        &self,
+        code: &CangjieCode,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<String>>;
+
+    /// Returns every character together with each of its codes.
+    ///
+    /// The characters are in candidate order,
+    /// and the codes of the same character are adjacent.
+    ///
+    /// # Errors
+    ///
+    /// Any error occurred in the backend.
+    fn entries(
+        &self,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<(String, CangjieCode)>>;
+
+    /// Returns the information about a character,
+    /// or `None` if the character is unknown.
+    ///
+    /// # Errors
+    ///
+    /// Any error occurred in the backend.
+    fn character_info(&self, character: &str) -> HowToTypeResult<Option<CharacterInfo>>;
+
+    /// Returns every character together with each of its codes and its filter flags,
+    /// i.e. the flags that [`CangjieFilter::matches`] checks.
+    ///
+    /// The entries are ordered as in [`CangjieDatabase::entries`] without a filter.
+    /// The default implementation queries [`CangjieDatabase::entries`] once per flag.
+    ///
+    /// # Errors
+    ///
+    /// Any error occurred in the backend.
+    fn entries_with_flags(
+        &self,
+        version: CangjieVersion,
+    ) -> HowToTypeResult<Vec<(String, CangjieCode, CangjieFilter)>> {
+        let mut flags = HashMap::<String, CangjieFilter>::new();
+        for flag in CangjieFilter::all().iter() {
+            for (character, _) in self.entries(version, flag)? {
+                *flags.entry(character).or_insert(CangjieFilter::empty()) |= flag;
+            }
+        }
+
+        let entries = self
+            .entries(version, CangjieFilter::empty())?
+            .into_iter()
+            .map(|(character, code)| {
+                let flags = flags
+                    .get(&character)
+                    .copied()
+                    .unwrap_or(CangjieFilter::empty());
+                (character, code, flags)
+            })
+            .collect();
+
+        Ok(entries)
+    }
+
+    /// Returns every character whose code matches a pattern, together with that code.
+    ///
+    /// The results are ordered by code and then in candidate order.
+    ///
+    /// # Errors
+    ///
+    /// Any error occurred in the backend.
+    fn search(
+        &self,
+        pattern: &CodePattern,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<(String, CangjieCode)>> {
+        let mut result = self
+            .entries(version, filter)?
+            .into_iter()
+            .filter(|(_, code)| pattern.matches(code))
+            .collect::<Vec<_>>();
+        result.sort_by(|(_, a), (_, b)| a.cmp(b));
+
+        Ok(result)
+    }
+
+    /// Returns the characters having any code matching a pattern, in candidate order.
+    ///
+    /// Each character appears only once.
+    ///
+    /// # Errors
+    ///
+    /// Any error occurred in the backend.
+    fn characters_matching(
+        &self,
+        pattern: &CodePattern,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<String>> {
+        let mut result = Vec::<String>::new();
+        for (character, code) in self.entries(version, filter)? {
+            if pattern.matches(&code) && !result.contains(&character) {
+                result.push(character);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// The entrypoint of the library.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # use libcangjie_howtotype::{
+/// #     CangjieCode, CangjieVersion, LibCangjieHowToType
+/// # };
+/// #
+/// # #[cfg(feature = "sqlite")]
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let cangjie = LibCangjieHowToType::new()?;
+///
+/// let how_to_type = cangjie.how_to_type("喵", CangjieVersion::V3)?;
+/// assert_eq!(*how_to_type, [CangjieCode::from_radicals("口廿田")]);
+/// #
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "sqlite"))]
+/// # fn main() {}
+/// ```
+///
+/// The data comes from a [`CangjieDatabase`] backend,
+/// which is libcangjie's SQLite database ([`SqliteDatabase`]) by default.
+/// Use [`LibCangjieHowToType::from_database`] for other backends.
+/// Without the `sqlite` feature, there is no default,
+/// and the backend must be named, e.g. `LibCangjieHowToType<EmbeddedCangjie>`.
+#[derive(Debug)]
+pub struct LibCangjieHowToType<
+    #[cfg(feature = "sqlite")] D = SqliteDatabase,
+    #[cfg(not(feature = "sqlite"))] D,
+> {
+    database: D,
+    #[cfg(feature = "cache")]
+    cache: Option<LookupCache>,
+}
+
+#[cfg(feature = "sqlite")]
+impl LibCangjieHowToType {
+    /// Creates a new `LibCangjieHowToType`
+    /// using the first database found in the search path.
+    ///
+    /// See [`LibCangjieHowToType::search_paths`] for the search order.
+    /// This is equivalent to `LibCangjieHowToType::builder().open()`.
+    ///
+    /// # Errors
+    ///
+    /// - [`NewError::NotFound`] if no database is found in the search path.
+    /// - [`NewError::IncompatibleSchema`] if the database is not a libcangjie database.
+    /// - [`NewError::DBError`] if the database connection fails.
+    pub fn new() -> NewResult<Self> {
+        Self::builder().open()
+    }
+
+    /// Creates a new [`SharedCangjie`], which can be shared across threads,
+    /// using the first database found in the search path.
+    ///
+    /// This is equivalent to `LibCangjieHowToType::builder().open_shared()`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`LibCangjieHowToType::new`].
+    pub fn new_shared() -> NewResult<SharedCangjie> {
+        Self::builder().open_shared()
+    }
+
+    /// Returns the paths searched for the database, in order.
+    ///
+    /// The search path consists of:
+    ///
+    /// 1. The path in the `LIBCANGJIE_DB` environment variable, if set.
+    /// 2. `$XDG_DATA_HOME/libcangjie/cangjie.db`.
+    /// 3. `libcangjie/cangjie.db` under each directory in `$XDG_DATA_DIRS`.
+    /// 4. `/usr/share/libcangjie/cangjie.db`.
+    ///
+    /// `$XDG_DATA_HOME` and `$XDG_DATA_DIRS` default to
+    /// `$HOME/.local/share` and `/usr/local/share:/usr/share` respectively,
+    /// as specified by the XDG Base Directory Specification.
+    /// Relative directories are ignored, and duplicate paths are removed.
+    #[must_use]
+    pub fn search_paths() -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+
+        if let Some(path) = env::var_os(DB_PATH_ENV).filter(|path| !path.is_empty()) {
+            paths.push(PathBuf::from(path));
+        }
+
+        let data_home = env::var_os("XDG_DATA_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var_os("HOME")
+                    .filter(|dir| !dir.is_empty())
+                    .map(|dir| Path::new(&dir).join(".local/share"))
+            });
+        let data_dirs = env::var_os("XDG_DATA_DIRS")
+            .filter(|dirs| !dirs.is_empty())
+            .map_or_else(
+                || {
+                    vec![
+                        PathBuf::from("/usr/local/share"),
+                        PathBuf::from("/usr/share"),
+                    ]
+                },
+                |dirs| env::split_paths(&dirs).collect(),
+            );
+        paths.extend(
+            data_home
+                .into_iter()
+                .chain(data_dirs)
+                .filter(|dir| dir.is_absolute())
+                .map(|dir| dir.join(DB_PATH_IN_DATA_DIR)),
+        );
+
+        paths.push(DB_PATH.to_path_buf());
+
+        let mut deduped = Vec::with_capacity(paths.len());
+        for path in paths {
+            if !deduped.contains(&path) {
+                deduped.push(path);
+            }
+        }
+        deduped
+    }
+
+    /// Returns a builder for configuring how the database is opened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # use libcangjie_howtotype::LibCangjieHowToType;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let cangjie = LibCangjieHowToType::builder()
+    ///     .path("/usr/share/libcangjie/cangjie.db")
+    ///     .open()?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn builder() -> LibCangjieHowToTypeBuilder {
+        LibCangjieHowToTypeBuilder::new()
+    }
+
+    /// Creates a new `LibCangjieHowToType` from an existing database connection.
+    ///
+    /// The connection is expected to point to a libcangjie database.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # use libcangjie_howtotype::LibCangjieHowToType;
+    /// use libcangjie_howtotype::rusqlite::{Connection, OpenFlags};
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let db_conn = Connection::open_with_flags(
+    ///     "/usr/share/libcangjie/cangjie.db",
+    ///     OpenFlags::SQLITE_OPEN_READ_ONLY,
+    /// )?;
+    /// let cangjie = LibCangjieHowToType::from_connection(db_conn);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub const fn from_connection(db_conn: Connection) -> Self {
+        Self::from_database(SqliteDatabase::from_connection(db_conn))
+    }
+
+    /// Creates a new `LibCangjieHowToType` from the contents of a libcangjie database file,
+    /// e.g. from `include_bytes!`, without needing the file at runtime.
+    ///
+    /// The bytes are copied into a read-only in-memory SQLite database
+    /// with SQLite's deserialize facility.
+    ///
+    /// # Errors
+    ///
+    /// - [`NewError::IncompatibleSchema`] if the database is not a libcangjie database.
+    /// - [`NewError::DBError`] if the bytes are not an SQLite database.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # use std::fs;
+    /// #
+    /// # use libcangjie_howtotype::{CangjieCode, CangjieVersion, LibCangjieHowToType};
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let bytes = fs::read("/usr/share/libcangjie/cangjie.db")?;
+    /// let cangjie = LibCangjieHowToType::from_bytes(bytes)?;
+    ///
+    /// let how_to_type = cangjie.how_to_type("喵", CangjieVersion::V3)?;
+    /// assert_eq!(*how_to_type, [CangjieCode::from_radicals("口廿田")]);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_bytes(bytes: impl AsRef<[u8]>) -> NewResult<Self> {
+        let database = SqliteDatabase::deserialize(bytes.as_ref())?;
+        database.check_schema()?;
+
+        Ok(Self::from_database(database))
+    }
+}
+
+impl<D> LibCangjieHowToType<D> {
+    /// Creates a new `LibCangjieHowToType` with the given backend.
+    #[must_use]
+    pub const fn from_database(database: D) -> Self {
+        Self {
+            database,
+            #[cfg(feature = "cache")]
+            cache: None,
+        }
+    }
+
+    /// Puts a cache in front of the backend,
+    /// holding the results of up to `capacity` lookups.
+    ///
+    /// The cache remembers the codes of recently looked up characters,
+    /// separately for each version and filter,
+    /// so that [`LibCangjieHowToType::how_to_type`] and friends
+    /// (including [`LibCangjieHowToType::how_to_type_text`])
+    /// answer repeated lookups without querying the backend.
+    /// When the cache is full, the least recently used lookup is evicted.
+    /// Reverse lookups and searches are not cached.
+    ///
+    /// This replaces any existing cache.
+    /// Requires the `cache` feature (enabled by default).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # use std::num::NonZeroUsize;
+    /// #
+    /// # use libcangjie_howtotype::{CangjieVersion, LibCangjieHowToType};
+    /// #
+    /// # #[cfg(feature = "sqlite")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let cangjie = LibCangjieHowToType::new()?.with_cache(NonZeroUsize::new(1000).unwrap());
+    ///
+    /// for _ in 0..3 {
+    ///     cangjie.how_to_type("喵", CangjieVersion::V3)?;
+    /// }
+    ///
+    /// let stats = cangjie.stats();
+    /// assert_eq!((stats.hits, stats.misses), (2, 1));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "sqlite"))]
+    /// # fn main() {}
+    /// ```
+    #[cfg(feature = "cache")]
+    #[must_use]
+    pub fn with_cache(mut self, capacity: NonZeroUsize) -> Self {
+        self.cache = Some(LookupCache::new(capacity));
+        self
+    }
+
+    /// Returns the statistics of the cache.
+    ///
+    /// Without a cache (see [`LibCangjieHowToType::with_cache`]),
+    /// all the statistics are zero.
+    #[cfg(feature = "cache")]
+    #[must_use]
+    pub fn stats(&self) -> CacheStats {
+        self.cache
+            .as_ref()
+            .map_or_else(CacheStats::default, LookupCache::stats)
+    }
+
+    /// Empties the cache and resets its statistics, if there is a cache.
+    #[cfg(feature = "cache")]
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
+    }
+
+    /// Returns the backend.
+    #[must_use]
+    pub const fn database(&self) -> &D {
+        &self.database
+    }
+
+    /// Consumes the `LibCangjieHowToType`, returning the backend.
+    #[must_use]
+    pub fn into_database(self) -> D {
+        self.database
+    }
+}
+
+impl<D: CangjieDatabase> LibCangjieHowToType<D> {
+    /// Queries how to type a character.
+    ///
+    /// This method returns all possible ways to type the given character.
+    /// If it doesn't know how to type the character,
+    /// it returns an empty vector.
+    ///
+    /// Note that the capacity of the [`SmallVec`]
+    /// is not part of the stable API.
+    ///
+    /// # Errors
+    ///
+    /// - [`HowToTypeError::DBError`] if the database query fails.
+    /// - [`HowToTypeError::UnexpectedValueType`] or [`HowToTypeError::InvalidCode`]
+    ///   if the database contains unexpected data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # use libcangjie_howtotype::{
+    /// #     CangjieCode, CangjieVersion, LibCangjieHowToType
+    /// # };
+    /// #
+    /// # #[cfg(feature = "sqlite")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let cangjie = LibCangjieHowToType::new()?;
+    ///
+    /// let how_to_type = cangjie.how_to_type("喵", CangjieVersion::V3)?;
+    /// assert_eq!(*how_to_type, [CangjieCode::from_radicals("口廿田")]);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "sqlite"))]
+    /// # fn main() {}
+    /// ```
+    pub fn how_to_type(
+        &self,
+        character: &str,
+        version: CangjieVersion,
+    ) -> HowToTypeResult<SmallVec<[CangjieCode; 1]>> {
+        self.how_to_type_with_filter(character, version, CangjieFilter::empty())
+    }
+
+    /// Queries how to type a character,
+    /// as seen by an input method configured with a filter.
+    ///
+    /// This is the same as [`LibCangjieHowToType::how_to_type`],
+    /// except that it returns an empty vector
+    /// if the character does not pass the filter.
+    ///
+    /// # Errors
+    ///
+    /// - [`HowToTypeError::DBError`] if the database query fails.
+    /// - [`HowToTypeError::UnexpectedValueType`] or [`HowToTypeError::InvalidCode`]
+    ///   if the database contains unexpected data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # use libcangjie_howtotype::{
+    /// #     CangjieCode, CangjieFilter, CangjieVersion, LibCangjieHowToType
+    /// # };
+    /// #
+    /// # #[cfg(feature = "sqlite")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let cangjie = LibCangjieHowToType::new()?;
+    ///
+    /// let how_to_type =
+    ///     cangjie.how_to_type_with_filter("喵", CangjieVersion::V3, CangjieFilter::CHINESE)?;
+    /// assert_eq!(*how_to_type, [CangjieCode::from_radicals("口廿田")]);
+    ///
+    /// let how_to_type =
+    ///     cangjie.how_to_type_with_filter("喵", CangjieVersion::V3, CangjieFilter::HIRAGANA)?;
+    /// assert!(how_to_type.is_empty());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "sqlite"))]
+    /// # fn main() {}
+    /// ```
+    pub fn how_to_type_with_filter(
+        &self,
+        character: &str,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<SmallVec<[CangjieCode; 1]>> {
+        let lookup = || {
+            self.database
+                .codes_for_character(character, version, filter)
+        };
+
+        #[cfg(feature = "cache")]
+        if let Some(cache) = &self.cache {
+            return cache.codes_for_character(character, version, filter, lookup);
+        }
+
+        lookup()
+    }
+
+    /// Queries how to type a character,
+    /// together with its position in the candidate list for each code.
+    ///
+    /// Since several characters may share the same code,
+    /// input methods list them as candidates in libcangjie's candidate order,
+    /// and the character has to be picked from that list.
+    /// The positions are counted among the characters passing the filter,
+    /// as in [`LibCangjieHowToType::characters_for_with_filter`].
+    /// If it doesn't know how to type the character,
+    /// or if the character does not pass the filter,
+    /// it returns an empty vector.
+    ///
+    /// Note that the capacity of the [`SmallVec`]
+    /// is not part of the stable API.
+    ///
+    /// # Errors
+    ///
+    /// - [`HowToTypeError::DBError`] if the database query fails.
+    /// - [`HowToTypeError::UnexpectedValueType`] or [`HowToTypeError::InvalidCode`]
+    ///   if the database contains unexpected data.
+    /// - [`HowToTypeError::NotACandidate`] if the backend does not list the character
+    ///   among the candidates of one of its codes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # use libcangjie_howtotype::{
+    /// #     CangjieCode, CangjieFilter, CangjieVersion, LibCangjieHowToType
+    /// # };
+    /// #
+    /// # #[cfg(feature = "sqlite")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let cangjie = LibCangjieHowToType::new()?;
+    ///
+    /// let how_to_type =
+    ///     cangjie.how_to_type_with_positions("喵", CangjieVersion::V3, CangjieFilter::empty())?;
+    /// assert_eq!(how_to_type[0].code, CangjieCode::from_radicals("口廿田"));
+    /// assert_eq!(how_to_type[0].position, 1);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "sqlite"))]
+    /// # fn main() {}
+    /// ```
+    pub fn how_to_type_with_positions(
+        &self,
+        character: &str,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<SmallVec<[CandidateCode; 1]>> {
+        let mut result = SmallVec::new();
+        for code in self.how_to_type_with_filter(character, version, filter)? {
+            let position = self
+                .characters_for_with_filter(&code, version, filter)?
+                .iter()
+                .position(|candidate| candidate == character)
+                .ok_or_else(|| HowToTypeError::NotACandidate {
+                    character: character.to_owned(),
+                    code: code.clone(),
+                })?
+                + 1;
+
+            result.push(CandidateCode { code, position });
+        }
+
+        Ok(result)
+    }
+
+    /// Queries how to type every character in a text.
+    ///
+    /// The text is split into segments, each being a character
+    /// together with any variation selectors following it.
+    /// The returned iterator yields every segment in order
+    /// with all possible ways to type it, as [`LibCangjieHowToType::how_to_type`] does.
+    /// If a segment with variation selectors is not in the database,
+    /// the ways to type its base character are returned instead.
+    /// Segments that it doesn't know how to type,
+    /// such as spaces and emojis, come with an empty vector.
+    ///
+    /// Each segment is looked up when the iterator advances.
+    ///
+    /// # Errors
+    ///
+    /// The iterator yields an error for a segment
+    /// if the lookup of that segment fails,
+    /// with the same variants as [`LibCangjieHowToType::how_to_type`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # use libcangjie_howtotype::{
+    /// #     CangjieCode, CangjieVersion, LibCangjieHowToType
+    /// # };
+    /// #
+    /// # #[cfg(feature = "sqlite")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let cangjie = LibCangjieHowToType::new()?;
+    ///
+    /// let annotated = cangjie
+    ///     .how_to_type_text("喵 喵", CangjieVersion::V3)
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    /// assert_eq!(annotated.len(), 3);
+    /// assert_eq!(annotated[0].0, "喵");
+    /// assert_eq!(*annotated[0].1, [CangjieCode::from_radicals("口廿田")]);
+    /// assert_eq!(annotated[1].0, " ");
+    /// assert!(annotated[1].1.is_empty());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "sqlite"))]
+    /// # fn main() {}
+    /// ```
+    pub fn how_to_type_text<'a, 'b>(
+        &'a self,
+        text: &'b str,
+        version: CangjieVersion,
+    ) -> HowToTypeText<'a, 'b, D> {
+        HowToTypeText {
+            cangjie: self,
+            segments: Segments(text),
+            version,
+        }
+    }
+
+    /// Queries how to type a character with Quick (速成).
+    ///
+    /// Quick codes are derived from the Cangjie 3 codes
+    /// (see [`CangjieCode::quick`]),
+    /// so this method does not take a [`CangjieVersion`].
+    /// Since many characters share the same Quick code,
+    /// each code comes with the position of the character
+    /// in the candidate list for that code.
+    /// If it doesn't know how to type the character,
+    /// it returns an empty vector.
+    ///
+    /// Note that the capacity of the [`SmallVec`]
+    /// is not part of the stable API.
+    ///
+    /// # Errors
+    ///
+    /// - [`HowToTypeError::DBError`] if the database query fails.
+    /// - [`HowToTypeError::UnexpectedValueType`] or [`HowToTypeError::InvalidCode`]
+    ///   if the database contains unexpected data.
+    /// - [`HowToTypeError::NotACandidate`] if the backend does not list the character
+    ///   among the candidates of one of its Quick codes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # use libcangjie_howtotype::{CangjieCode, LibCangjieHowToType};
+    /// #
+    /// # #[cfg(feature = "sqlite")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let cangjie = LibCangjieHowToType::new()?;
+    ///
+    /// let how_to_type = cangjie.how_to_type_quick("喵")?;
+    /// assert_eq!(how_to_type[0].code, CangjieCode::from_radicals("口田"));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "sqlite"))]
+    /// # fn main() {}
+    /// ```
+    pub fn how_to_type_quick(&self, character: &str) -> HowToTypeResult<SmallVec<[QuickCode; 1]>> {
+        let mut result = SmallVec::<[QuickCode; 1]>::new();
+        for code in self.how_to_type(character, CangjieVersion::V3)? {
+            let code = code.quick();
+            if result.iter().any(|quick_code| quick_code.code == code) {
+                continue;
+            }
+
+            let position = self
+                .quick_candidates(&code)?
+                .iter()
+                .position(|candidate| candidate == character)
+                .ok_or_else(|| HowToTypeError::NotACandidate {
+                    character: character.to_owned(),
+                    code: code.clone(),
+                })?
+                + 1;
+
+            result.push(QuickCode { code, position });
+        }
+
+        Ok(result)
+    }
+
+    /// Lists the characters that can be typed with a Quick code,
+    /// in candidate order.
+    fn quick_candidates(&self, code: &CangjieCode) -> HowToTypeResult<Vec<String>> {
+        let pattern = match **code {
+            [first, last] => CodePattern::from(
+                &[
+                    CodePatternElement::Radical(first),
+                    CodePatternElement::AnyRadicals,
+                    CodePatternElement::Radical(last),
+                ][..],
+            ),
+            _ => CodePattern::from(code),
+        };
+
+        self.database
+            .characters_matching(&pattern, CangjieVersion::V3, CangjieFilter::empty())
+    }
+
+    /// Queries the information about a character.
+    ///
+    /// If the character is not in the database, it returns `None`.
+    ///
+    /// # Errors
+    ///
+    /// - [`HowToTypeError::DBError`] if the database query fails.
+    /// - [`HowToTypeError::UnexpectedValueType`]
+    ///   if the database contains unexpected data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # use libcangjie_howtotype::LibCangjieHowToType;
+    /// #
+    /// # #[cfg(feature = "sqlite")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let cangjie = LibCangjieHowToType::new()?;
+    ///
+    /// let info = cangjie.character_info("喵")?.expect("喵 is in the database");
+    /// assert!(info.chinese);
+    /// assert!(!info.hiragana);
+    ///
+    /// assert_eq!(cangjie.character_info("😀")?, None);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "sqlite"))]
+    /// # fn main() {}
+    /// ```
+    pub fn character_info(&self, character: &str) -> HowToTypeResult<Option<CharacterInfo>> {
+        self.database.character_info(character)
+    }
+
+    /// Queries which characters can be typed with a code.
+    ///
+    /// This is the reverse of [`LibCangjieHowToType::how_to_type`],
+    /// i.e., what an input method does.
+    /// The characters are returned in libcangjie's candidate order,
+    /// with the most frequent characters first.
+    /// If no character can be typed with the code,
+    /// it returns an empty vector.
+    ///
+    /// # Errors
+    ///
+    /// - [`HowToTypeError::DBError`] if the database query fails.
+    /// - [`HowToTypeError::UnexpectedValueType`] or [`HowToTypeError::InvalidCode`]
+    ///   if the database contains unexpected data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # use libcangjie_howtotype::{
+    /// #     CangjieCode, CangjieVersion, LibCangjieHowToType
+    /// # };
+    /// #
+    /// # #[cfg(feature = "sqlite")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let cangjie = LibCangjieHowToType::new()?;
+    ///
+    /// let characters = cangjie.characters_for(
+    ///     &CangjieCode::from_radicals("口廿田"),
+    ///     CangjieVersion::V3,
+    /// )?;
+    /// assert!(characters.iter().any(|character| character == "喵"));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "sqlite"))]
+    /// # fn main() {}
+    /// ```
+    pub fn characters_for(
+        &self,
+        code: &CangjieCode,
+        version: CangjieVersion,
+    ) -> HowToTypeResult<Vec<String>> {
+        self.characters_for_with_filter(code, version, CangjieFilter::empty())
+    }
+
+    /// Queries which characters can be typed with a code,
+    /// as seen by an input method configured with a filter.
+    ///
+    /// This is the same as [`LibCangjieHowToType::characters_for`],
+    /// except that only the characters passing the filter are returned.
+    ///
+    /// # Errors
+    ///
+    /// - [`HowToTypeError::DBError`] if the database query fails.
+    /// - [`HowToTypeError::UnexpectedValueType`] or [`HowToTypeError::InvalidCode`]
+    ///   if the database contains unexpected data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # use libcangjie_howtotype::{
+    /// #     CangjieCode, CangjieFilter, CangjieVersion, LibCangjieHowToType
+    /// # };
+    /// #
+    /// # #[cfg(feature = "sqlite")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let cangjie = LibCangjieHowToType::new()?;
+    ///
+    /// let characters = cangjie.characters_for_with_filter(
+    ///     &CangjieCode::from_radicals("口廿田"),
+    ///     CangjieVersion::V3,
+    ///     CangjieFilter::BIG5 | CangjieFilter::HKSCS,
+    /// )?;
+    /// assert!(characters.iter().any(|character| character == "喵"));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "sqlite"))]
+    /// # fn main() {}
+    /// ```
+    pub fn characters_for_with_filter(
+        &self,
+        code: &CangjieCode,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<String>> {
+        self.database.characters_for_code(code, version, filter)
+    }
+
+    /// Searches for characters whose code matches a pattern.
+    ///
+    /// This method returns every character together with its matching code,
+    /// ordered by code and then by libcangjie's candidate order.
+    /// A character appears more than once if several of its codes match.
+    ///
+    /// # Errors
+    ///
+    /// - [`HowToTypeError::DBError`] if the database query fails.
+    /// - [`HowToTypeError::UnexpectedValueType`] or [`HowToTypeError::InvalidCode`]
+    ///   if the database contains unexpected data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # use libcangjie_howtotype::{
+    /// #     CangjieCode, CangjieVersion, CodePattern, LibCangjieHowToType
+    /// # };
+    /// #
+    /// # #[cfg(feature = "sqlite")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let cangjie = LibCangjieHowToType::new()?;
+    ///
+    /// let results = cangjie.search(&CodePattern::from_radicals("口*田"), CangjieVersion::V3)?;
+    /// assert!(results.contains(&(String::from("喵"), CangjieCode::from_radicals("口廿田"))));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "sqlite"))]
+    /// # fn main() {}
+    /// ```
+    pub fn search(
+        &self,
+        pattern: &CodePattern,
+        version: CangjieVersion,
+    ) -> HowToTypeResult<Vec<(String, CangjieCode)>> {
+        self.search_with_filter(pattern, version, CangjieFilter::empty())
+    }
+
+    /// Searches for characters whose code matches a pattern,
+    /// as seen by an input method configured with a filter.
+    ///
+    /// This is the same as [`LibCangjieHowToType::search`],
+    /// except that only the characters passing the filter are returned.
+    ///
+    /// # Errors
+    ///
+    /// - [`HowToTypeError::DBError`] if the database query fails.
+    /// - [`HowToTypeError::UnexpectedValueType`] or [`HowToTypeError::InvalidCode`]
+    ///   if the database contains unexpected data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # use libcangjie_howtotype::{
+    /// #     CangjieCode, CangjieFilter, CangjieVersion, CodePattern, LibCangjieHowToType
+    /// # };
+    /// #
+    /// # #[cfg(feature = "sqlite")]
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let cangjie = LibCangjieHowToType::new()?;
+    ///
+    /// let results = cangjie.search_with_filter(
+    ///     &CodePattern::from_radicals("口*田"),
+    ///     CangjieVersion::V3,
+    ///     CangjieFilter::CHINESE,
+    /// )?;
+    /// assert!(results.contains(&(String::from("喵"), CangjieCode::from_radicals("口廿田"))));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "sqlite"))]
+    /// # fn main() {}
+    /// ```
+    pub fn search_with_filter(
+        &self,
+        pattern: &CodePattern,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<(String, CangjieCode)>> {
+        self.database.search(pattern, version, filter)
+    }
+}
+
+/// Iterator returned by [`LibCangjieHowToType::how_to_type_text`].
+#[derive(Debug)]
+pub struct HowToTypeText<
+    'a,
+    'b,
+    #[cfg(feature = "sqlite")] D = SqliteDatabase,
+    #[cfg(not(feature = "sqlite"))] D,
+> {
+    cangjie: &'a LibCangjieHowToType<D>,
+    segments: Segments<'b>,
+    version: CangjieVersion,
+}
+
+impl<'b, D: CangjieDatabase> Iterator for HowToTypeText<'_, 'b, D> {
+    type Item = HowToTypeResult<(&'b str, SmallVec<[CangjieCode; 1]>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let segment = self.segments.next()?;
+
+        let result = self
+            .cangjie
+            .how_to_type(segment, self.version)
+            .and_then(|how_to_type| {
+                let base_len = segment.chars().next().map_or(0, char::len_utf8);
+                if how_to_type.is_empty() && base_len < segment.len() {
+                    self.cangjie.how_to_type(&segment[..base_len], self.version)
+                } else {
+                    Ok(how_to_type)
+                }
+            });
+
+        Some(result.map(|how_to_type| (segment, how_to_type)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.segments.size_hint()
+    }
+}
+
+/// Iterator over the segments of a text,
+/// each being a character followed by any variation selectors.
+#[derive(Debug)]
+struct Segments<'a>(&'a str);
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chars = self.0.char_indices();
+        chars.next()?;
+
+        let end = chars
+            .find(|&(_, c)| !is_variation_selector(c))
+            .map_or(self.0.len(), |(index, _)| index);
+        let (segment, rest) = self.0.split_at(end);
+        self.0 = rest;
+
+        Some(segment)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::from(!self.0.is_empty()), Some(self.0.len()))
+    }
+}
+
+/// Returns whether a character is a variation selector,
+/// including the ideographic variation selectors.
+const fn is_variation_selector(c: char) -> bool {
+    matches!(c, '\u{FE00}'..='\u{FE0F}' | '\u{E0100}'..='\u{E01EF}')
+}
+
+/// Builder for [`LibCangjieHowToType`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # use libcangjie_howtotype::LibCangjieHowToTypeBuilder;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let cangjie = LibCangjieHowToTypeBuilder::new()
+///     .path("/usr/share/libcangjie/cangjie.db")
+///     .open()?;
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "sqlite")]
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default)]
+pub struct LibCangjieHowToTypeBuilder {
+    path: Option<PathBuf>,
+    cipher: CipherSettings,
+    #[cfg(feature = "cache")]
+    cache_capacity: Option<NonZeroUsize>,
+}
+
+#[cfg(feature = "sqlite")]
+impl LibCangjieHowToTypeBuilder {
+    /// Creates a new builder with the default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the path to the database.
+    ///
+    /// If not set, the first existing file in
+    /// [`LibCangjieHowToType::search_paths`] is used.
+    #[must_use]
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Sets the key to decrypt a database encrypted with SQLCipher.
+    ///
+    /// The key is passed to SQLCipher's `PRAGMA key`,
+    /// so it is a passphrase,
+    /// or a raw key in hexadecimal written as `x'…'`.
+    /// The key is checked when the database is opened.
+    ///
+    /// This requires one of the `rusqlite-*sqlcipher` features.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// #
+    /// # use libcangjie_howtotype::{LibCangjieHowToType, NewError};
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let result = LibCangjieHowToType::builder()
+    ///     .path("cangjie-encrypted.db")
+    ///     .key("correct horse battery staple")
+    ///     .open();
+    /// let cangjie = match result {
+    ///     Err(NewError::WrongKey) => panic!("Wrong key"),
+    ///     result => result?,
+    /// };
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(any(
+        feature = "rusqlite-sqlcipher",
+        feature = "rusqlite-bundled-sqlcipher",
+        feature = "rusqlite-bundled-sqlcipher-vendored-openssl",
+    ))]
+    #[must_use]
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.cipher.key = Some(key.into());
+        self
+    }
+
+    /// Sets the SQLCipher major version whose default settings
+    /// the database was encrypted with, e.g. `3` for SQLCipher 3.
+    ///
+    /// This is passed to SQLCipher's `PRAGMA cipher_compatibility`.
+    /// If not set, the defaults of the SQLCipher linked are used.
+    /// It has no effect without [`LibCangjieHowToTypeBuilder::key`].
+    #[cfg(any(
+        feature = "rusqlite-sqlcipher",
+        feature = "rusqlite-bundled-sqlcipher",
+        feature = "rusqlite-bundled-sqlcipher-vendored-openssl",
+    ))]
+    #[must_use]
+    pub const fn cipher_compatibility(mut self, version: u8) -> Self {
+        self.cipher.compatibility = Some(version);
+        self
+    }
+
+    /// Puts a cache holding up to `capacity` lookups in front of the database.
+    ///
+    /// See [`LibCangjieHowToType::with_cache`].
+    /// If not set, there is no cache.
+    #[cfg(feature = "cache")]
+    #[must_use]
+    pub const fn cache_capacity(mut self, capacity: NonZeroUsize) -> Self {
+        self.cache_capacity = Some(capacity);
+        self
+    }
+
+    /// Opens the database and creates a [`LibCangjieHowToType`].
+    ///
+    /// # Errors
+    ///
+    /// - [`NewError::NotFound`] if no path is set
+    ///   and no database is found in the search path.
+    /// - [`NewError::WrongKey`] if a key is set
+    ///   and the database cannot be decrypted with it.
+    /// - [`NewError::IncompatibleSchema`] if the database is not a libcangjie database.
+    /// - [`NewError::DBError`] if the database connection fails.
+    pub fn open(self) -> NewResult<LibCangjieHowToType> {
+        let database = self.open_database(&self.resolve_path()?)?;
+
+        Ok(self.finish(LibCangjieHowToType::from_database(database)))
+    }
+
+    /// Opens the database and creates a [`SharedCangjie`],
+    /// which can be shared across threads.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`LibCangjieHowToTypeBuilder::open`].
+    pub fn open_shared(self) -> NewResult<SharedCangjie> {
+        let path = self.resolve_path()?;
+        let database = self.open_database(&path)?;
+        let pool = SqlitePool::from_database(path, self.cipher.clone(), database);
+
+        Ok(self.finish(LibCangjieHowToType::from_database(pool)))
+    }
+
+    /// Opens a connection to the database, decrypting it if a key is set.
+    fn open_database(&self, path: &Path) -> NewResult<SqliteDatabase> {
+        let database = SqliteDatabase::open(path, &self.cipher).map_err(|error| match error {
+            rusqlite::Error::SqliteFailure(
+                rusqlite::ffi::Error {
+                    code: rusqlite::ffi::ErrorCode::NotADatabase,
+                    ..
+                },
+                _,
+            ) if self.cipher.key.is_some() => NewError::WrongKey,
+            error => NewError::DBError(error),
+        })?;
+        database.check_schema()?;
+
+        Ok(database)
+    }
+
+    /// Applies the settings other than the path.
+    fn finish<D>(&self, cangjie: LibCangjieHowToType<D>) -> LibCangjieHowToType<D> {
+        #[cfg(feature = "cache")]
+        if let Some(capacity) = self.cache_capacity {
+            return cangjie.with_cache(capacity);
+        }
+
+        cangjie
+    }
+
+    /// Returns the path set, or the first existing file in the search path.
+    fn resolve_path(&self) -> NewResult<PathBuf> {
+        if let Some(path) = &self.path {
+            return Ok(path.clone());
+        }
+
+        let tried = LibCangjieHowToType::search_paths();
+        match tried.iter().find(|path| path.is_file()) {
+            Some(path) => Ok(path.clone()),
+            None => Err(NewError::NotFound { tried }),
+        }
+    }
+}
+
+/// Error type for [`LibCangjieHowToType::new`].
+#[cfg(feature = "sqlite")]
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum NewError {
+    /// Database error.
+    #[error("Database error")]
+    DBError(#[from] rusqlite::Error),
+    /// The database is not found in any of the searched paths.
+    #[error("Database not found (tried: {})", format_paths(.tried))]
+    NotFound {
+        /// The paths that were tried, in order.
+        tried: Vec<PathBuf>,
+    },
+    /// The database cannot be decrypted with the key given.
+    ///
+    /// This is also the error if a key is given for an unencrypted database.
+    #[error("Wrong key for the database")]
+    WrongKey,
+    /// The database does not have the tables and columns of libcangjie's database.
+    #[error(
+        "Incompatible database schema ({generation}, missing: {}, versions: {})",
+        .missing.join(", "),
+        format_versions(.versions),
+    )]
+    IncompatibleSchema {
+        /// The schema generation the database comes from.
+        generation: SchemaGeneration,
+        /// The tables (`table`) and columns (`table.column`) missing.
+        missing: Vec<String>,
+        /// The distinct versions in `codes.version`, in ascending order,
+        /// or empty if that column is missing.
+        versions: Vec<i64>,
+    },
+    /// The Tokio runtime is shutting down, so the database cannot be opened.
+    #[cfg(feature = "async")]
+    #[error("The Tokio runtime is shutting down")]
+    RuntimeShutdown,
+}
+
+/// The generation of libcangjie's database schema that a database comes from,
+/// as reported by [`NewError::IncompatibleSchema`].
+#[cfg(feature = "sqlite")]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum SchemaGeneration {
+    /// The schema of libcangjie 1.x, with the `chars` and `codes` tables.
+    ///
+    /// This is the schema read by this crate.
+    /// A database is of this generation if one of the tables has all the columns read,
+    /// and is incompatible if the other table lacks some of them.
+    LibCangjie1,
+    /// Neither the `chars` nor the `codes` table has all the columns read,
+    /// so the database is not from libcangjie.
+    Unknown,
+}
+
+#[cfg(feature = "sqlite")]
+impl Display for SchemaGeneration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LibCangjie1 => write!(f, "libcangjie 1.x schema"),
+            Self::Unknown => write!(f, "unknown schema"),
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+fn format_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(feature = "sqlite")]
+fn format_versions(versions: &[i64]) -> String {
+    if versions.is_empty() {
+        return "none".to_owned();
+    }
+
+    versions
+        .iter()
+        .map(i64::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A specialised [`Result`] type for [`LibCangjieHowToType::new`].
+#[cfg(feature = "sqlite")]
+pub type NewResult<T> = Result<T, NewError>;
+
+/// Error type for the query methods of [`LibCangjieHowToType`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum HowToTypeError {
+    /// Database error.
+    #[cfg(feature = "sqlite")]
+    #[error("Database error")]
+    DBError(#[from] rusqlite::Error),
+    /// A value in the database is not of the expected type.
+    #[error("Unexpected value type in column {column} for character {char_index}")]
+    UnexpectedValueType {
+        /// The `char_index` of the offending row.
+        char_index: i64,
+        /// The column containing the value.
+        column: &'static str,
+    },
+    /// A code in the database is not a valid Cangjie code.
+    #[error("Invalid code {raw:?} for character {char_index}")]
+    InvalidCode {
+        /// The `char_index` of the offending row.
+        char_index: i64,
+        /// The code as stored in the database.
+        raw: String,
+    },
+    /// The backend does not list a character among the candidates of its own code.
+    #[error("Character {character:?} is not a candidate of its own code {}", .code.codes())]
+    NotACandidate {
+        /// The character looked up.
+        character: String,
+        /// The code of the character whose candidates lack it.
+        code: CangjieCode,
+    },
+    /// The Tokio runtime is shutting down, so the query cannot run.
+    #[cfg(feature = "async")]
+    #[error("The Tokio runtime is shutting down")]
+    RuntimeShutdown,
+}
+
+/// A specialised [`Result`] type for the query methods of [`LibCangjieHowToType`].
+pub type HowToTypeResult<T> = Result<T, HowToTypeError>;
@@ -0,0 +1,287 @@
+//! The data embedded at build time with the `embedded-data` feature.
+//!
+//! `build.rs` generates the data from libcangjie's database as static tables:
+//!
+//! - `CHARACTERS`: all characters, in candidate order.
+//! - `BY_CHARACTER`: the positions in `CHARACTERS`, sorted by character.
+//! - `V3` and `V5`: the [`CodeTable`] of each version.
+//!
+//! The tables are searched in place, so there is nothing to decode at runtime.
+
+use smallvec::SmallVec;
+
+use crate::{
+    CangjieCode, CangjieDatabase, CangjieFilter, CangjieRadical, CangjieVersion, CharacterInfo,
+    CodePattern, HowToTypeResult, LibCangjieHowToType,
+};
+
+/// The tables generated by `build.rs`.
+mod data {
+    use super::{Character, CodeTable};
+    use crate::CangjieFilter;
+    use crate::CangjieRadical::*;
+
+    include!(concat!(env!("OUT_DIR"), "/embedded.rs"));
+}
+
+/// The backend reading the data embedded at build time.
+///
+/// This requires the `embedded-data` feature.
+/// The data comes from libcangjie's database at build time
+/// (from `LIBCANGJIE_DB`, or `/usr/share/libcangjie/cangjie.db`),
+/// so the database is not needed at runtime.
+/// The data is stored as sorted static tables and searched in place,
+/// so creating this backend costs nothing.
+///
+/// # Examples
+///
+/// ```
+/// # use libcangjie_howtotype::{CangjieCode, CangjieDatabase, CangjieFilter, CangjieVersion, EmbeddedCangjie};
+/// #
+/// let codes = EmbeddedCangjie
+///     .codes_for_character("喵", CangjieVersion::V3, CangjieFilter::empty())
+///     .unwrap();
+/// assert_eq!(*codes, [CangjieCode::from_radicals("口廿田")]);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct EmbeddedCangjie;
+
+impl EmbeddedCangjie {
+    /// Returns the position of a character in candidate order.
+    fn position(character: &str) -> Option<usize> {
+        data::BY_CHARACTER
+            .binary_search_by(|&position| {
+                data::CHARACTERS[position as usize].character.cmp(character)
+            })
+            .ok()
+            .map(|index| data::BY_CHARACTER[index] as usize)
+    }
+
+    /// Returns whether the character at a position passes the filter.
+    fn passes(position: usize, filter: CangjieFilter) -> bool {
+        filter.matches_flags(data::CHARACTERS[position].flags)
+    }
+
+    /// Iterates over the codes matching a pattern
+    /// together with the positions of their characters passing the filter,
+    /// ordered by code and then in candidate order.
+    fn matching(
+        pattern: &CodePattern,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> impl Iterator<Item = (usize, &'static [CangjieRadical])> {
+        CodeTable::of(version)
+            .starting_with(&pattern.literal_prefix())
+            .filter(move |&(position, code)| {
+                pattern.matches(code) && Self::passes(position, filter)
+            })
+    }
+}
+
+impl CangjieDatabase for EmbeddedCangjie {
+    fn codes_for_character(
+        &self,
+        character: &str,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<SmallVec<[CangjieCode; 1]>> {
+        let codes = Self::position(character)
+            .filter(|&position| Self::passes(position, filter))
+            .map(|position| {
+                CodeTable::of(version)
+                    .codes_at(position)
+                    .iter()
+                    .map(|&(_, code)| CangjieCode::from(code))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(codes)
+    }
+
+    fn characters_for_code(
+        &self,
+        code: &CangjieCode,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<String>> {
+        let characters = CodeTable::of(version)
+            .starting_with(code)
+            .take_while(|&(_, candidate)| candidate == &**code)
+            .filter(|&(position, _)| Self::passes(position, filter))
+            .map(|(position, _)| data::CHARACTERS[position].character.to_owned())
+            .collect();
+
+        Ok(characters)
+    }
+
+    fn entries(
+        &self,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<(String, CangjieCode)>> {
+        let entries = CodeTable::of(version)
+            .entries
+            .iter()
+            .filter(|&&(position, _)| Self::passes(position as usize, filter))
+            .map(|&(position, code)| {
+                let character = data::CHARACTERS[position as usize].character;
+                (character.to_owned(), CangjieCode::from(code))
+            })
+            .collect();
+
+        Ok(entries)
+    }
+
+    fn character_info(&self, character: &str) -> HowToTypeResult<Option<CharacterInfo>> {
+        Ok(Self::position(character).map(|position| data::CHARACTERS[position].info()))
+    }
+
+    fn entries_with_flags(
+        &self,
+        version: CangjieVersion,
+    ) -> HowToTypeResult<Vec<(String, CangjieCode, CangjieFilter)>> {
+        let entries = CodeTable::of(version)
+            .entries
+            .iter()
+            .map(|&(position, code)| {
+                let character = &data::CHARACTERS[position as usize];
+                let code = CangjieCode::from(code);
+                (character.character.to_owned(), code, character.flags)
+            })
+            .collect();
+
+        Ok(entries)
+    }
+
+    fn search(
+        &self,
+        pattern: &CodePattern,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<(String, CangjieCode)>> {
+        let results = Self::matching(pattern, version, filter)
+            .map(|(position, code)| {
+                let character = data::CHARACTERS[position].character;
+                (character.to_owned(), CangjieCode::from(code))
+            })
+            .collect();
+
+        Ok(results)
+    }
+
+    fn characters_matching(
+        &self,
+        pattern: &CodePattern,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<String>> {
+        let mut positions = Self::matching(pattern, version, filter)
+            .map(|(position, _)| position)
+            .collect::<Vec<_>>();
+        positions.sort_unstable();
+        positions.dedup();
+
+        Ok(positions
+            .into_iter()
+            .map(|position| data::CHARACTERS[position].character.to_owned())
+            .collect())
+    }
+}
+
+impl LibCangjieHowToType<EmbeddedCangjie> {
+    /// Creates a new `LibCangjieHowToType` using the data embedded at build time.
+    ///
+    /// This requires the `embedded-data` feature,
+    /// and does not need libcangjie's database at runtime.
+    /// See [`EmbeddedCangjie`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libcangjie_howtotype::{CangjieCode, CangjieVersion, EmbeddedCangjie, LibCangjieHowToType};
+    /// #
+    /// let cangjie = LibCangjieHowToType::<EmbeddedCangjie>::embedded();
+    ///
+    /// let how_to_type = cangjie.how_to_type("喵", CangjieVersion::V3).unwrap();
+    /// assert_eq!(*how_to_type, [CangjieCode::from_radicals("口廿田")]);
+    /// ```
+    #[must_use]
+    pub const fn embedded() -> Self {
+        Self::from_database(EmbeddedCangjie)
+    }
+}
+
+/// A character in the embedded data.
+struct Character {
+    character: &'static str,
+    simplified: Option<&'static str>,
+    flags: CangjieFilter,
+    frequency: u32,
+}
+
+impl Character {
+    fn info(&self) -> CharacterInfo {
+        CharacterInfo {
+            character: self.character.to_owned(),
+            simplified: self.simplified.map(String::from),
+            chinese: self.flags.contains(CangjieFilter::CHINESE),
+            big5: self.flags.contains(CangjieFilter::BIG5),
+            hkscs: self.flags.contains(CangjieFilter::HKSCS),
+            zhuyin: self.flags.contains(CangjieFilter::ZHUYIN),
+            kanji: self.flags.contains(CangjieFilter::KANJI),
+            hiragana: self.flags.contains(CangjieFilter::HIRAGANA),
+            katakana: self.flags.contains(CangjieFilter::KATAKANA),
+            punctuation: self.flags.contains(CangjieFilter::PUNCTUATION),
+            symbol: self.flags.contains(CangjieFilter::SYMBOLS),
+            frequency: self.frequency,
+        }
+    }
+}
+
+/// The codes of one Cangjie version in the embedded data.
+struct CodeTable {
+    /// All codes with the positions of their characters,
+    /// sorted by position and then by code.
+    entries: &'static [(u32, &'static [CangjieRadical])],
+    /// The indices in `entries`, sorted by code and then by position.
+    by_code: &'static [u32],
+}
+
+impl CodeTable {
+    /// Returns the table of a version.
+    const fn of(version: CangjieVersion) -> &'static Self {
+        match version {
+            CangjieVersion::V3 => &data::V3,
+            CangjieVersion::V5 => &data::V5,
+        }
+    }
+
+    /// Returns the entries of the character at a position.
+    fn codes_at(&self, position: usize) -> &'static [(u32, &'static [CangjieRadical])] {
+        let start = self
+            .entries
+            .partition_point(|&(candidate, _)| (candidate as usize) < position);
+        let len =
+            self.entries[start..].partition_point(|&(candidate, _)| candidate as usize == position);
+
+        &self.entries[start..start + len]
+    }
+
+    /// Iterates over the codes starting with a prefix
+    /// together with the positions of their characters,
+    /// ordered by code and then in candidate order.
+    fn starting_with(
+        &'static self,
+        prefix: &[CangjieRadical],
+    ) -> impl Iterator<Item = (usize, &'static [CangjieRadical])> + use<> {
+        let code = |index: u32| self.entries[index as usize].1;
+        let start = self.by_code.partition_point(|&index| code(index) < prefix);
+        let len = self.by_code[start..].partition_point(|&index| code(index).starts_with(prefix));
+
+        self.by_code[start..start + len].iter().map(|&index| {
+            let (position, code) = self.entries[index as usize];
+            (position as usize, code)
+        })
+    }
+}
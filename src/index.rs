@@ -0,0 +1,408 @@
+//! The FST index of codes, with the `fst` feature.
+
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
+
+use ::fst::{Automaton, IntoStreamer, Map, MapBuilder, Streamer};
+use smallvec::SmallVec;
+use thiserror::Error;
+
+use crate::{
+    CangjieCode, CangjieDatabase, CangjieFilter, CangjieRadical, CangjieVersion, CodePattern,
+    CodePatternElement, HowToTypeResult,
+};
+
+/// Index of all codes as a finite-state transducer (FST),
+/// for fast exact, prefix and wildcard searches by code.
+///
+/// The index is a plain byte buffer,
+/// which can be written to a file with [`as_bytes`](Self::as_bytes)
+/// and later loaded with [`new`](Self::new) from anything holding the bytes,
+/// in particular a memory map shared across processes.
+///
+/// Each entry is keyed by the version (`3` or `5`),
+/// the code in codes (e.g. `rtw`), a NUL byte and the character,
+/// and maps to the position of the character in candidate order
+/// among the characters of the version (the upper 32 bits)
+/// and its [`CangjieFilter`] flags (the lower 32 bits).
+///
+/// Results are ordered as in [`CangjieDatabase`]:
+/// by code, and then in candidate order.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "sqlite")]
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # use libcangjie_howtotype::{
+/// #     CangjieCode, CangjieFilter, CangjieVersion, CodePattern, FstIndex, LibCangjieHowToType,
+/// # };
+/// #
+/// let cangjie = LibCangjieHowToType::new()?;
+/// let index = FstIndex::build(cangjie.database())?;
+///
+/// // Write it to a file, and load it later, e.g. with a memory map.
+/// let index = FstIndex::new(index.as_bytes())?;
+///
+/// let results = index.search(
+///     &CodePattern::from_codes(b"r*w"),
+///     CangjieVersion::V3,
+///     CangjieFilter::empty(),
+/// )?;
+/// assert!(results.contains(&("喵".to_string(), CangjieCode::from_codes(b"rtw"))));
+/// #
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "sqlite"))]
+/// # fn main() {}
+/// ```
+pub struct FstIndex<D = Vec<u8>> {
+    map: Map<D>,
+}
+
+impl FstIndex {
+    /// Builds the index from all codes in a backend.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the backend.
+    pub fn build(database: &impl CangjieDatabase) -> HowToTypeResult<Self> {
+        let mut keys = Vec::new();
+        for version in [CangjieVersion::V3, CangjieVersion::V5] {
+            let mut positions = HashMap::new();
+            for (character, code, flags) in database.entries_with_flags(version)? {
+                let len = positions.len();
+                let position = *positions.entry(character.clone()).or_insert(len);
+                keys.push((key(version, &code, &character), position, flags.bits()));
+            }
+        }
+        keys.sort_unstable();
+        keys.dedup_by(|(a, ..), (b, ..)| a == b);
+
+        let mut builder = MapBuilder::memory();
+        for (key, position, flags) in keys {
+            builder
+                .insert(key, (position as u64) << 32 | u64::from(flags))
+                .expect("keys are sorted and unique");
+        }
+        let data = builder.into_inner().expect("writing to memory cannot fail");
+
+        Ok(Self {
+            map: Map::new(data).expect("the index has just been built"),
+        })
+    }
+}
+
+impl<D: AsRef<[u8]>> FstIndex<D> {
+    /// Loads an index from its bytes, as returned by [`as_bytes`](Self::as_bytes).
+    ///
+    /// Only the structure of the FST is checked here.
+    /// Invalid entries are reported when they are queried.
+    ///
+    /// # Errors
+    ///
+    /// [`IndexError::Fst`] if the bytes are not an FST.
+    ///
+    /// # Examples
+    ///
+    /// Memory-mapping an index file with [memmap2](https://docs.rs/memmap2):
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # use std::fs::File;
+    /// #
+    /// # use libcangjie_howtotype::FstIndex;
+    /// # use memmap2::Mmap;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let file = File::open("cangjie.fst")?;
+    /// // SAFETY: The file is not modified while it is mapped.
+    /// let index = FstIndex::new(unsafe { Mmap::map(&file)? })?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(data: D) -> IndexResult<Self> {
+        Ok(Self {
+            map: Map::new(data)?,
+        })
+    }
+
+    /// Returns the bytes of the index, e.g. for writing to a file.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.map.as_fst().as_bytes()
+    }
+
+    /// Returns the underlying bytes.
+    #[must_use]
+    pub fn into_inner(self) -> D {
+        self.map.into_fst().into_inner()
+    }
+
+    /// Returns the number of entries, i.e. pairs of a character and a code.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns whether the index has no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Finds the characters typed with a code, in candidate order.
+    ///
+    /// # Errors
+    ///
+    /// [`IndexError::InvalidEntry`] if the index contains an invalid entry.
+    pub fn characters_for_code(
+        &self,
+        code: &CangjieCode,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> IndexResult<Vec<String>> {
+        let start = key(version, code, "");
+        let mut end = start.clone();
+        *end.last_mut().expect("keys are not empty") += 1;
+
+        let entries = collect(self.map.range().ge(start).lt(end).into_stream(), filter)?;
+        Ok(entries
+            .into_iter()
+            .map(|(character, _)| character)
+            .collect())
+    }
+
+    /// Finds the characters whose code starts with a prefix,
+    /// together with the codes.
+    ///
+    /// # Errors
+    ///
+    /// [`IndexError::InvalidEntry`] if the index contains an invalid entry.
+    pub fn starting_with(
+        &self,
+        prefix: &CangjieCode,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> IndexResult<Vec<(String, CangjieCode)>> {
+        let mut start = key(version, prefix, "");
+        start.pop();
+        let mut end = start.clone();
+        // Codes consist of ASCII letters, so all of them are below 0xFF.
+        end.push(0xFF);
+
+        collect(self.map.range().ge(start).lt(end).into_stream(), filter)
+    }
+
+    /// Finds the characters whose code matches a pattern,
+    /// together with the codes.
+    ///
+    /// Unlike [`CangjieDatabase::search`],
+    /// this only visits the parts of the index that can match,
+    /// so patterns starting with wildcards are fast as well.
+    ///
+    /// # Errors
+    ///
+    /// [`IndexError::InvalidEntry`] if the index contains an invalid entry.
+    pub fn search(
+        &self,
+        pattern: &CodePattern,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> IndexResult<Vec<(String, CangjieCode)>> {
+        let automaton = PatternAutomaton {
+            version: version_byte(version),
+            pattern,
+        };
+
+        collect(self.map.search(automaton).into_stream(), filter)
+    }
+}
+
+impl<D: AsRef<[u8]>> Debug for FstIndex<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FstIndex")
+            .field("len", &self.map.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Error type for loading and querying an [`FstIndex`].
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum IndexError {
+    /// The bytes are not an FST.
+    #[error("Invalid FST")]
+    Fst(#[from] ::fst::Error),
+    /// An entry in the index is not a version, a code and a character.
+    #[error("Invalid entry {key:?} in the index")]
+    InvalidEntry {
+        /// The key of the offending entry.
+        key: Vec<u8>,
+    },
+}
+
+/// A specialised [`Result`] type for [`FstIndex`].
+pub type IndexResult<T> = Result<T, IndexError>;
+
+/// Returns the byte a version is keyed by.
+const fn version_byte(version: CangjieVersion) -> u8 {
+    match version {
+        CangjieVersion::V3 => b'3',
+        CangjieVersion::V5 => b'5',
+    }
+}
+
+/// Decodes the entries of a stream passing the filter,
+/// ordered by code and then in candidate order.
+fn collect(
+    mut stream: impl for<'a> Streamer<'a, Item = (&'a [u8], u64)>,
+    filter: CangjieFilter,
+) -> IndexResult<Vec<(String, CangjieCode)>> {
+    let mut entries = Vec::new();
+    while let Some((key, value)) = stream.next() {
+        let flags = CangjieFilter::from_bits_retain(value as u32);
+        if !filter.is_empty() && !filter.intersects(flags) {
+            continue;
+        }
+
+        let (character, code) =
+            decode_key(key).ok_or_else(|| IndexError::InvalidEntry { key: key.to_vec() })?;
+        entries.push((code, value >> 32, character));
+    }
+    entries.sort_unstable_by(|(a, a_position, _), (b, b_position, _)| {
+        a.cmp(b).then(a_position.cmp(b_position))
+    });
+
+    Ok(entries
+        .into_iter()
+        .map(|(code, _, character)| (character, code))
+        .collect())
+}
+
+/// Returns the key of an entry.
+fn key(version: CangjieVersion, code: &CangjieCode, character: &str) -> Vec<u8> {
+    let mut key = Vec::with_capacity(code.len() + character.len() + 2);
+    key.push(version_byte(version));
+    key.extend(code.iter().map(|radical| radical.to_code()));
+    key.push(0);
+    key.extend(character.as_bytes());
+    key
+}
+
+/// Decodes the character and the code from a key.
+fn decode_key(key: &[u8]) -> Option<(String, CangjieCode)> {
+    let (_, rest) = key.split_first()?;
+    let separator = rest.iter().position(|&byte| byte == 0)?;
+    let code = rest[..separator]
+        .iter()
+        .map(|&code| CangjieRadical::try_from_code(code))
+        .collect::<Option<_>>()?;
+    let character = String::from_utf8(rest[separator + 1..].to_vec()).ok()?;
+
+    Some((character, code))
+}
+
+/// Matches the keys of a version whose code matches a pattern.
+struct PatternAutomaton<'a> {
+    version: u8,
+    pattern: &'a CodePattern,
+}
+
+/// The state of [`PatternAutomaton`].
+#[derive(Clone)]
+enum PatternState {
+    /// Expecting the version.
+    Version,
+    /// In the code, at the sorted positions in the pattern.
+    Code(SmallVec<[usize; 8]>),
+    /// In the character, after the code matched.
+    Character,
+    /// Cannot match any more.
+    Dead,
+}
+
+impl PatternAutomaton<'_> {
+    /// Adds a position in the pattern,
+    /// and the positions after any `*` from there, which may match nothing.
+    fn insert(&self, positions: &mut SmallVec<[usize; 8]>, mut position: usize) {
+        loop {
+            positions.push(position);
+            match self.pattern.get(position) {
+                Some(CodePatternElement::AnyRadicals) => position += 1,
+                _ => break,
+            }
+        }
+    }
+
+    /// Returns the state with the given positions, sorted and deduplicated.
+    fn code(mut positions: SmallVec<[usize; 8]>) -> PatternState {
+        if positions.is_empty() {
+            return PatternState::Dead;
+        }
+        positions.sort_unstable();
+        positions.dedup();
+
+        PatternState::Code(positions)
+    }
+}
+
+impl Automaton for PatternAutomaton<'_> {
+    type State = PatternState;
+
+    fn start(&self) -> Self::State {
+        PatternState::Version
+    }
+
+    fn is_match(&self, state: &Self::State) -> bool {
+        matches!(state, PatternState::Character)
+    }
+
+    fn can_match(&self, state: &Self::State) -> bool {
+        !matches!(state, PatternState::Dead)
+    }
+
+    fn will_always_match(&self, state: &Self::State) -> bool {
+        matches!(state, PatternState::Character)
+    }
+
+    fn accept(&self, state: &Self::State, byte: u8) -> Self::State {
+        match state {
+            PatternState::Version if byte == self.version => {
+                let mut positions = SmallVec::new();
+                self.insert(&mut positions, 0);
+                Self::code(positions)
+            }
+            PatternState::Code(positions) if byte == 0 => {
+                if positions.contains(&self.pattern.len()) {
+                    PatternState::Character
+                } else {
+                    PatternState::Dead
+                }
+            }
+            PatternState::Code(positions) => {
+                let Some(radical) = CangjieRadical::try_from_code(byte) else {
+                    return PatternState::Dead;
+                };
+
+                let mut next = SmallVec::new();
+                for &position in positions {
+                    match self.pattern.get(position) {
+                        Some(&CodePatternElement::Radical(expected)) if expected == radical => {
+                            self.insert(&mut next, position + 1);
+                        }
+                        Some(CodePatternElement::AnyRadical) => {
+                            self.insert(&mut next, position + 1);
+                        }
+                        Some(CodePatternElement::AnyRadicals) => self.insert(&mut next, position),
+                        _ => {}
+                    }
+                }
+                Self::code(next)
+            }
+            PatternState::Character => PatternState::Character,
+            PatternState::Version | PatternState::Dead => PatternState::Dead,
+        }
+    }
+}
@@ -3,13 +3,12 @@
 //! # Examples
 //!
 //! ```
-//! # use std::error::Error;
-//! #
+//! # #[cfg(feature = "sqlite")]
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! use libcangjie_howtotype::{
 //!     CangjieCode, CangjieVersion, LibCangjieHowToType
 //! };
-//! #
-//! # fn main() -> Result<(), Box<dyn Error>> {
+//!
 //! let cangjie = LibCangjieHowToType::new()?;
 //!
 //! let how_to_type = cangjie.how_to_type("喵", CangjieVersion::V3)?;
@@ -17,25 +16,95 @@
 //! #
 //! # Ok(())
 //! # }
+//! # #[cfg(not(feature = "sqlite"))]
+//! # fn main() {}
 //! ```
+//!
+//! # Features
+//!
+//! - `std` (default): [`LibCangjieHowToType`] and the backends.
+//!   Without it, only the radical, code and pattern types are available,
+//!   which work with `no_std` and `alloc`.
+//! - `sqlite` (default): the backend reading libcangjie's SQLite database.
+//! - `cache` (default): the lookup cache of `LibCangjieHowToType::with_cache`.
+//! - `embedded-data`: the data embedded at build time,
+//!   see `EmbeddedCangjie`.
+//! - `async`: `AsyncCangjie`, running the queries on Tokio's blocking thread pool.
+//! - `fst`: `FstIndex`, an index of the codes
+//!   for fast prefix and wildcard searches.
+//! - `serde`: serialisation of the radical, code and result types,
+//!   see the `serde` module.
+//! - `rusqlite-sqlcipher`, `rusqlite-bundled-sqlcipher`
+//!   and `rusqlite-bundled-sqlcipher-vendored-openssl`:
+//!   SQLCipher support, to open encrypted databases
+//!   with `LibCangjieHowToTypeBuilder::key`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use std::borrow::{Borrow, BorrowMut};
-use std::fmt::{self, Display, Formatter};
-use std::iter::Copied;
-use std::ops::{Deref, DerefMut};
-use std::path::Path;
-use std::slice::Iter;
-use std::sync::LazyLock;
+extern crate alloc;
 
-use rusqlite::types::ValueRef;
-use rusqlite::{Connection, OpenFlags};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::borrow::{Borrow, BorrowMut};
+use core::error::Error;
+use core::fmt::{self, Display, Formatter};
+use core::iter::Copied;
+use core::ops::{Deref, DerefMut};
+use core::slice::Iter;
+use core::str::FromStr;
+
+use bitflags::bitflags;
 use smallvec::SmallVec;
-use thiserror::Error;
 
+#[cfg(feature = "fst")]
+pub use fst;
+#[cfg(feature = "sqlite")]
 pub use rusqlite;
 pub use smallvec;
 
-static DB_PATH: LazyLock<&Path> = LazyLock::new(|| Path::new("/usr/share/libcangjie/cangjie.db"));
+#[cfg(feature = "async")]
+pub use asynchronous::AsyncCangjie;
+#[cfg(feature = "cache")]
+pub use cache::CacheStats;
+#[cfg(feature = "std")]
+pub use database::{
+    CangjieDatabase, HowToTypeError, HowToTypeResult, HowToTypeText, LibCangjieHowToType,
+};
+#[cfg(feature = "sqlite")]
+pub use database::{LibCangjieHowToTypeBuilder, NewError, NewResult, SchemaGeneration};
+#[cfg(feature = "embedded-data")]
+pub use embedded::EmbeddedCangjie;
+#[cfg(feature = "fst")]
+pub use index::{FstIndex, IndexError, IndexResult};
+#[cfg(feature = "sqlite")]
+pub use memory::InMemoryCangjie;
+pub use packed::{CodeTooLongError, PackedCangjieCode, PackedRadicals};
+#[cfg(feature = "sqlite")]
+pub use shared::{SharedCangjie, SqlitePool};
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteDatabase;
+
+#[cfg(feature = "async")]
+mod asynchronous;
+#[cfg(feature = "cache")]
+mod cache;
+#[cfg(feature = "std")]
+mod database;
+#[cfg(feature = "embedded-data")]
+mod embedded;
+#[cfg(feature = "fst")]
+mod index;
+#[cfg(feature = "sqlite")]
+mod memory;
+mod packed;
+#[cfg(feature = "sqlite")]
+mod schema;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "sqlite")]
+mod shared;
+#[cfg(feature = "sqlite")]
+mod sqlite;
 
 /// Cangjie radical.
 ///
@@ -104,6 +173,7 @@ pub enum CangjieRadical {
     /// Ｚ.
     ///
     /// This follows libcangjie's behaviour.
+    /// See [`ZRadical`] for printing it as "重" instead.
     Z,
 }
 
@@ -123,7 +193,16 @@ impl CangjieRadical {
     /// ```
     #[must_use]
     pub const fn from_code(code: u8) -> Self {
-        match code {
+        match Self::try_from_code(code) {
+            Some(radical) => radical,
+            None => panic!("Invalid Cangjie radical code"),
+        }
+    }
+
+    /// Parses the code used by libcangjie (abcdefg…wxyz),
+    /// returning `None` if the code is invalid.
+    const fn try_from_code(code: u8) -> Option<Self> {
+        Some(match code {
             b'a' => Self::A,
             b'b' => Self::B,
             b'c' => Self::C,
@@ -150,14 +229,14 @@ impl CangjieRadical {
             b'x' => Self::X,
             b'y' => Self::Y,
             b'z' => Self::Z,
-            _ => panic!("Invalid Cangjie radical code"),
-        }
+            _ => return None,
+        })
     }
 
     /// Parses the radical (日月金木水火土…田難卜Ｚ).
     ///
-    /// Note that, following libcangjie's behaviour,
-    /// the radical for `Z` is "Ｚ" instead of "重".
+    /// The radical for `Z` may be given as either "Ｚ",
+    /// following libcangjie's behaviour, or "重".
     ///
     /// # Panics
     ///
@@ -169,18 +248,21 @@ impl CangjieRadical {
     /// # use libcangjie_howtotype::CangjieRadical;
     /// #
     /// assert_eq!(CangjieRadical::from_radical('日'), CangjieRadical::A);
-    /// ```
-    ///
-    /// Attempting to parse "重" will panic.
-    ///
-    /// ```should_panic
-    /// # use libcangjie_howtotype::CangjieRadical;
-    /// #
-    /// let radical = CangjieRadical::from_radical('重');
+    /// assert_eq!(CangjieRadical::from_radical('Ｚ'), CangjieRadical::Z);
+    /// assert_eq!(CangjieRadical::from_radical('重'), CangjieRadical::Z);
     /// ```
     #[must_use]
     pub const fn from_radical(radical: char) -> Self {
-        match radical {
+        match Self::try_from_radical(radical) {
+            Some(radical) => radical,
+            None => panic!("Invalid Cangjie radical"),
+        }
+    }
+
+    /// Parses the radical (日月金木水火土…田難卜Ｚ),
+    /// returning `None` if the radical is invalid.
+    const fn try_from_radical(radical: char) -> Option<Self> {
+        Some(match radical {
             '日' => Self::A,
             '月' => Self::B,
             '金' => Self::C,
@@ -206,9 +288,9 @@ impl CangjieRadical {
             '田' => Self::W,
             '難' => Self::X,
             '卜' => Self::Y,
-            'Ｚ' => Self::Z,
-            _ => panic!("Invalid Cangjie radical"),
-        }
+            'Ｚ' | '重' => Self::Z,
+            _ => return None,
+        })
     }
 
     /// Returns the code used by libcangjie (abcdefg…wxyz).
@@ -256,6 +338,7 @@ impl CangjieRadical {
     ///
     /// Note that, following libcangjie's behaviour,
     /// the radical for `Z` is "Ｚ" instead of "重".
+    /// Use [`CangjieRadical::to_radical_with`] to choose otherwise.
     ///
     /// # Examples
     ///
@@ -296,6 +379,148 @@ impl CangjieRadical {
             Self::Z => 'Ｚ',
         }
     }
+
+    /// Returns the radical character,
+    /// printing the radical for `Z` as chosen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libcangjie_howtotype::{CangjieRadical, ZRadical};
+    /// #
+    /// assert_eq!(CangjieRadical::A.to_radical_with(ZRadical::Zhong), '日');
+    /// assert_eq!(CangjieRadical::Z.to_radical_with(ZRadical::FullWidthZ), 'Ｚ');
+    /// assert_eq!(CangjieRadical::Z.to_radical_with(ZRadical::Zhong), '重');
+    /// ```
+    #[must_use]
+    pub const fn to_radical_with(self, z_radical: ZRadical) -> char {
+        match self {
+            Self::Z => z_radical.to_char(),
+            _ => self.to_radical(),
+        }
+    }
+}
+
+/// How to print the radical for [`CangjieRadical::Z`].
+///
+/// Both forms are accepted when parsing radicals.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub enum ZRadical {
+    /// Ｚ, following libcangjie's behaviour.
+    #[default]
+    FullWidthZ,
+    /// 重, as used in printed textbooks.
+    Zhong,
+}
+
+impl ZRadical {
+    /// Returns the character for the radical.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libcangjie_howtotype::ZRadical;
+    /// #
+    /// assert_eq!(ZRadical::FullWidthZ.to_char(), 'Ｚ');
+    /// assert_eq!(ZRadical::Zhong.to_char(), '重');
+    /// ```
+    #[must_use]
+    pub const fn to_char(self) -> char {
+        match self {
+            Self::FullWidthZ => 'Ｚ',
+            Self::Zhong => '重',
+        }
+    }
+}
+
+impl TryFrom<u8> for CangjieRadical {
+    type Error = ParseRadicalError;
+
+    /// Parses the code used by libcangjie (abcdefg…wxyz),
+    /// also accepting upper-case letters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libcangjie_howtotype::CangjieRadical;
+    /// #
+    /// assert_eq!(CangjieRadical::try_from(b'a'), Ok(CangjieRadical::A));
+    /// assert_eq!(CangjieRadical::try_from(b'A'), Ok(CangjieRadical::A));
+    /// assert!(CangjieRadical::try_from(b'1').is_err());
+    /// ```
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::try_from_code(value.to_ascii_lowercase())
+            .ok_or_else(|| ParseRadicalError::new(char::from(value), 0))
+    }
+}
+
+impl FromStr for CangjieRadical {
+    type Err = ParseRadicalError;
+
+    /// Parses a single code or radical, as in [`CangjieRadical::try_from`].
+    ///
+    /// The string must consist of exactly one character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libcangjie_howtotype::CangjieRadical;
+    /// #
+    /// assert_eq!("a".parse(), Ok(CangjieRadical::A));
+    /// assert_eq!("Ａ".parse(), Ok(CangjieRadical::A));
+    /// assert_eq!("日".parse(), Ok(CangjieRadical::A));
+    /// assert!("".parse::<CangjieRadical>().is_err());
+    /// assert!("ab".parse::<CangjieRadical>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let radical = chars.next().ok_or(ParseRadicalError::missing(0))?;
+        if let Some(extra) = chars.next() {
+            return Err(ParseRadicalError::new(extra, 1));
+        }
+
+        Self::try_from(radical)
+    }
+}
+
+impl TryFrom<char> for CangjieRadical {
+    type Error = ParseRadicalError;
+
+    /// Parses either a code or a radical.
+    ///
+    /// Codes may be given as lower-case or upper-case letters,
+    /// in either ASCII or full-width forms (ａｂｃ…ｘｙｚ).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libcangjie_howtotype::CangjieRadical;
+    /// #
+    /// assert_eq!(CangjieRadical::try_from('a'), Ok(CangjieRadical::A));
+    /// assert_eq!(CangjieRadical::try_from('Ａ'), Ok(CangjieRadical::A));
+    /// assert_eq!(CangjieRadical::try_from('日'), Ok(CangjieRadical::A));
+    /// assert!(CangjieRadical::try_from('１').is_err());
+    /// ```
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        code_from_char(value)
+            .and_then(Self::try_from_code)
+            .or_else(|| Self::try_from_radical(value))
+            .ok_or_else(|| ParseRadicalError::new(value, 0))
+    }
+}
+
+/// Converts a letter in ASCII or full-width form, of either case,
+/// to the code used by libcangjie.
+fn code_from_char(c: char) -> Option<u8> {
+    let c = match c {
+        'ａ'..='ｚ' | 'Ａ'..='Ｚ' => char::from_u32(u32::from(c) - 0xFEE0)?,
+        _ => c,
+    };
+
+    u8::try_from(c)
+        .ok()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|code| code.to_ascii_lowercase())
 }
 
 /// Code that can be used to type a character.
@@ -353,8 +578,8 @@ impl CangjieCode {
 
     /// Parses a sequence of radicals (日月金木水火土…田難卜Ｚ).
     ///
-    /// Note that, following libcangjie's behaviour,
-    /// the radical for `Z` is "Ｚ" instead of "重".
+    /// The radical for `Z` may be given as either "Ｚ",
+    /// following libcangjie's behaviour, or "重".
     ///
     /// # Panics
     ///
@@ -373,18 +598,77 @@ impl CangjieCode {
     ///         CangjieRadical::W,
     ///     ][..]),
     /// );
+    /// assert_eq!(CangjieCode::from_radicals("重"), CangjieCode::from_radicals("Ｚ"));
+    /// ```
+    #[must_use]
+    pub fn from_radicals(radicals: &str) -> Self {
+        radicals.chars().map(CangjieRadical::from_radical).collect()
+    }
+
+    /// Parses a sequence of codes used by libcangjie (abcdefg…wxyz),
+    /// also accepting upper-case letters.
+    ///
+    /// # Errors
+    ///
+    /// [`ParseRadicalError`] carrying the first invalid byte and its index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libcangjie_howtotype::CangjieCode;
+    /// #
+    /// assert_eq!(
+    ///     CangjieCode::try_from_codes(b"RTW"),
+    ///     Ok(CangjieCode::from_codes(b"rtw")),
+    /// );
+    ///
+    /// let error = CangjieCode::try_from_codes(b"rt1").unwrap_err();
+    /// assert_eq!(error.character(), '1');
+    /// assert_eq!(error.position(), 2);
     /// ```
+    pub fn try_from_codes(codes: &[u8]) -> Result<Self, ParseRadicalError> {
+        codes
+            .iter()
+            .enumerate()
+            .map(|(position, &code)| {
+                CangjieRadical::try_from(code).map_err(|error| error.at(position))
+            })
+            .collect()
+    }
+
+    /// Parses a sequence of radicals (日月金木水火土…田難卜Ｚ).
+    ///
+    /// The radical for `Z` may be given as either "Ｚ",
+    /// following libcangjie's behaviour, or "重".
+    ///
+    /// # Errors
+    ///
+    /// [`ParseRadicalError`] carrying the first invalid character
+    /// and its index in characters.
     ///
-    /// Attempting to parse "重" will panic.
+    /// # Examples
     ///
-    /// ```should_panic
+    /// ```
     /// # use libcangjie_howtotype::CangjieCode;
     /// #
-    /// let code = CangjieCode::from_radicals("重");
+    /// assert_eq!(
+    ///     CangjieCode::try_from_radicals("口廿田"),
+    ///     Ok(CangjieCode::from_codes(b"rtw")),
+    /// );
+    ///
+    /// let error = CangjieCode::try_from_radicals("口廿喵").unwrap_err();
+    /// assert_eq!(error.character(), '喵');
+    /// assert_eq!(error.position(), 2);
     /// ```
-    #[must_use]
-    pub fn from_radicals(radicals: &str) -> Self {
-        radicals.chars().map(CangjieRadical::from_radical).collect()
+    pub fn try_from_radicals(radicals: &str) -> Result<Self, ParseRadicalError> {
+        radicals
+            .chars()
+            .enumerate()
+            .map(|(position, radical)| {
+                CangjieRadical::try_from_radical(radical)
+                    .ok_or_else(|| ParseRadicalError::new(radical, position))
+            })
+            .collect()
     }
 
     /// Returns a display adapter for printing the codes.
@@ -409,7 +693,8 @@ impl CangjieCode {
     /// Returns a display adapter for printing the radicals.
     ///
     /// Note that, following libcangjie's behaviour,
-    /// the radical for `Z` is "Ｚ" instead of "重".
+    /// the radical for `Z` is "Ｚ" instead of "重" by default.
+    /// Use [`Radicals::z_radical`] to choose otherwise.
     ///
     /// # Examples
     ///
@@ -430,7 +715,63 @@ impl CangjieCode {
     /// ```
     #[must_use]
     pub fn radicals(&self) -> Radicals<'_> {
-        Radicals(self)
+        Radicals {
+            code: self,
+            z_radical: ZRadical::default(),
+        }
+    }
+
+    /// Derives the Quick (速成) code,
+    /// which consists of the first and the last radicals of the code.
+    ///
+    /// Codes with fewer than two radicals are returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libcangjie_howtotype::CangjieCode;
+    /// #
+    /// assert_eq!(
+    ///     CangjieCode::from_radicals("口廿田").quick(),
+    ///     CangjieCode::from_radicals("口田"),
+    /// );
+    /// assert_eq!(
+    ///     CangjieCode::from_radicals("日").quick(),
+    ///     CangjieCode::from_radicals("日"),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn quick(&self) -> Self {
+        match **self {
+            [first, .., last] => Self::from(&[first, last][..]),
+            _ => self.clone(),
+        }
+    }
+}
+
+impl FromStr for CangjieCode {
+    type Err = ParseRadicalError;
+
+    /// Parses a sequence of codes and radicals.
+    ///
+    /// Each character is parsed as in [`CangjieRadical::try_from`],
+    /// so codes and radicals can be mixed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libcangjie_howtotype::CangjieCode;
+    /// #
+    /// let code = CangjieCode::from_codes(b"rtw");
+    /// assert_eq!("rtw".parse(), Ok(code.clone()));
+    /// assert_eq!("ＲＴＷ".parse(), Ok(code.clone()));
+    /// assert_eq!("口廿田".parse(), Ok(code));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.chars()
+            .enumerate()
+            .map(|(position, c)| CangjieRadical::try_from(c).map_err(|error| error.at(position)))
+            .collect()
     }
 }
 
@@ -530,152 +871,614 @@ impl Display for Codes<'_> {
 
 /// A display adapter for printing the radicals of a [`CangjieCode`].
 #[derive(Debug)]
-pub struct Radicals<'a>(&'a CangjieCode);
+pub struct Radicals<'a> {
+    code: &'a CangjieCode,
+    z_radical: ZRadical,
+}
+
+impl Radicals<'_> {
+    /// Chooses how to print the radical for `Z`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libcangjie_howtotype::{CangjieCode, ZRadical};
+    /// #
+    /// let code = CangjieCode::from_codes(b"zxaj");
+    /// assert_eq!(code.radicals().to_string(), "Ｚ難日十");
+    /// assert_eq!(
+    ///     code.radicals().z_radical(ZRadical::Zhong).to_string(),
+    ///     "重難日十",
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn z_radical(mut self, z_radical: ZRadical) -> Self {
+        self.z_radical = z_radical;
+        self
+    }
+}
 
 impl Display for Radicals<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        for radical in self.0 {
-            write!(f, "{}", radical.to_radical())?;
+        for radical in self.code {
+            write!(f, "{}", radical.to_radical_with(self.z_radical))?;
         }
 
         Ok(())
     }
 }
 
-/// Cangjie version.
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+/// Quick (速成) code of a character,
+/// as returned by [`LibCangjieHowToType::how_to_type_quick`].
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct QuickCode {
+    /// The Quick code.
+    pub code: CangjieCode,
+    /// The 1-based position of the character
+    /// among all characters sharing the Quick code.
+    pub position: usize,
+}
+
+/// A code of a character together with its candidate position,
+/// as returned by [`LibCangjieHowToType::how_to_type_with_positions`].
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct CandidateCode {
+    /// The code.
+    pub code: CangjieCode,
+    /// The 1-based position of the character
+    /// among all characters sharing the code.
+    pub position: usize,
+}
+
+/// Information about a character in libcangjie's database,
+/// as returned by [`LibCangjieHowToType::character_info`].
+///
+/// The flags determine whether the character is shown
+/// as a candidate by input methods using libcangjie,
+/// depending on the filters configured.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 #[non_exhaustive]
-pub enum CangjieVersion {
-    V3,
-    V5,
+pub struct CharacterInfo {
+    /// The character.
+    pub character: String,
+    /// The simplified form of the character, if the database records one.
+    pub simplified: Option<String>,
+    /// Whether the character is a (traditional) Chinese character.
+    pub chinese: bool,
+    /// Whether the character is in Big5.
+    pub big5: bool,
+    /// Whether the character is in HKSCS.
+    pub hkscs: bool,
+    /// Whether the character is a Zhuyin (Bopomofo) symbol.
+    pub zhuyin: bool,
+    /// Whether the character is a Japanese kanji.
+    pub kanji: bool,
+    /// Whether the character is a Japanese hiragana.
+    pub hiragana: bool,
+    /// Whether the character is a Japanese katakana.
+    pub katakana: bool,
+    /// Whether the character is a punctuation mark.
+    pub punctuation: bool,
+    /// Whether the character is a symbol.
+    pub symbol: bool,
+    /// The frequency of the character, used to order candidates.
+    pub frequency: u32,
+}
+
+/// Element of a [`CodePattern`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum CodePatternElement {
+    /// Matches exactly this radical.
+    Radical(CangjieRadical),
+    /// Matches any single radical (`?`).
+    AnyRadical,
+    /// Matches any sequence of radicals, including the empty one (`*`).
+    AnyRadicals,
 }
 
-/// The entrypoint of the library.
+/// Pattern for searching codes, possibly containing wildcards.
+///
+/// `?` matches any single radical,
+/// and `*` matches any sequence of radicals, including the empty one.
 ///
 /// # Examples
 ///
 /// ```
-/// # use std::error::Error;
-/// #
-/// # use libcangjie_howtotype::{
-/// #     CangjieCode, CangjieVersion, LibCangjieHowToType
-/// # };
+/// # use libcangjie_howtotype::{CangjieCode, CodePattern};
 /// #
-/// # fn main() -> Result<(), Box<dyn Error>> {
-/// let cangjie = LibCangjieHowToType::new()?;
+/// let pattern = CodePattern::from_codes(b"d*d");
+/// assert_eq!(CodePattern::from_radicals("木*木"), pattern);
+/// assert_eq!(pattern.codes().to_string(), "d*d");
+/// assert_eq!(pattern.radicals().to_string(), "木*木");
 ///
-/// let how_to_type = cangjie.how_to_type("喵", CangjieVersion::V3)?;
-/// assert_eq!(*how_to_type, [CangjieCode::from_radicals("口廿田")]);
-/// #
-/// # Ok(())
-/// # }
+/// assert!(pattern.matches(&CangjieCode::from_codes(b"dd")));
+/// assert!(pattern.matches(&CangjieCode::from_codes(b"dmd")));
+/// assert!(!pattern.matches(&CangjieCode::from_codes(b"dm")));
 /// ```
-#[derive(Debug)]
-pub struct LibCangjieHowToType {
-    db_conn: Connection,
-}
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default)]
+pub struct CodePattern(SmallVec<[CodePatternElement; 5]>);
 
-impl LibCangjieHowToType {
-    /// Creates a new `LibCangjieHowToType`.
+impl CodePattern {
+    /// Parses a pattern of codes used by libcangjie (abcdefg…wxyz)
+    /// and wildcards (`*` and `?`).
     ///
-    /// # Errors
+    /// # Panics
+    ///
+    /// Panics if any character in the pattern is neither
+    /// a valid Cangjie radical code nor a wildcard.
+    ///
+    /// # Examples
     ///
-    /// [`NewError::DBError`] if the database connection fails.
-    pub fn new() -> NewResult<Self> {
-        let db_conn = Connection::open_with_flags(
-            *DB_PATH,
-            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
-        )?;
+    /// ```
+    /// # use libcangjie_howtotype::{
+    /// #     CangjieRadical, CodePattern, CodePatternElement
+    /// # };
+    /// #
+    /// assert_eq!(
+    ///     CodePattern::from_codes(b"d?"),
+    ///     CodePattern::from(&[
+    ///         CodePatternElement::Radical(CangjieRadical::D),
+    ///         CodePatternElement::AnyRadical,
+    ///     ][..]),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn from_codes(codes: &[u8]) -> Self {
+        codes
+            .iter()
+            .map(|&code| match code {
+                b'?' => CodePatternElement::AnyRadical,
+                b'*' => CodePatternElement::AnyRadicals,
+                _ => CodePatternElement::Radical(CangjieRadical::from_code(code)),
+            })
+            .collect()
+    }
 
-        Ok(Self { db_conn })
+    /// Parses a pattern of radicals (日月金木水火土…田難卜Ｚ)
+    /// and wildcards (`*` and `?`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if any character in the pattern is neither
+    /// a valid Cangjie radical nor a wildcard.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libcangjie_howtotype::{
+    /// #     CangjieRadical, CodePattern, CodePatternElement
+    /// # };
+    /// #
+    /// assert_eq!(
+    ///     CodePattern::from_radicals("木?"),
+    ///     CodePattern::from(&[
+    ///         CodePatternElement::Radical(CangjieRadical::D),
+    ///         CodePatternElement::AnyRadical,
+    ///     ][..]),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn from_radicals(radicals: &str) -> Self {
+        radicals
+            .chars()
+            .map(|radical| match radical {
+                '?' => CodePatternElement::AnyRadical,
+                '*' => CodePatternElement::AnyRadicals,
+                _ => CodePatternElement::Radical(CangjieRadical::from_radical(radical)),
+            })
+            .collect()
     }
 
-    /// Queries how to type a character.
+    /// Returns whether the pattern contains any wildcard.
+    ///
+    /// # Examples
     ///
-    /// This method returns all possible ways to type the given character.
-    /// If it doesn't know how to type the character,
-    /// it returns an empty vector.
+    /// ```
+    /// # use libcangjie_howtotype::CodePattern;
+    /// #
+    /// assert!(CodePattern::from_codes(b"d*d").has_wildcard());
+    /// assert!(!CodePattern::from_codes(b"dd").has_wildcard());
+    /// ```
+    #[must_use]
+    pub fn has_wildcard(&self) -> bool {
+        self.0
+            .iter()
+            .any(|element| !matches!(element, CodePatternElement::Radical(_)))
+    }
+
+    /// Returns whether the pattern matches a code.
     ///
-    /// Note that the capacity of the [`SmallVec`]
-    /// is not part of the stable API.
+    /// # Examples
     ///
-    /// # Errors
+    /// ```
+    /// # use libcangjie_howtotype::{CangjieCode, CodePattern};
+    /// #
+    /// let pattern = CodePattern::from_codes(b"a?c*");
+    /// assert!(pattern.matches(&CangjieCode::from_codes(b"abc")));
+    /// assert!(pattern.matches(&CangjieCode::from_codes(b"abcde")));
+    /// assert!(!pattern.matches(&CangjieCode::from_codes(b"ac")));
+    /// ```
+    #[must_use]
+    pub fn matches(&self, code: &[CangjieRadical]) -> bool {
+        // Classic wildcard matching with backtracking to the last `*`.
+        let (mut pattern_pos, mut code_pos) = (0, 0);
+        let mut backtrack = None;
+
+        while code_pos < code.len() {
+            match self.0.get(pattern_pos) {
+                Some(CodePatternElement::AnyRadicals) => {
+                    backtrack = Some((pattern_pos, code_pos));
+                    pattern_pos += 1;
+                }
+                Some(CodePatternElement::AnyRadical) => {
+                    pattern_pos += 1;
+                    code_pos += 1;
+                }
+                Some(&CodePatternElement::Radical(radical)) if radical == code[code_pos] => {
+                    pattern_pos += 1;
+                    code_pos += 1;
+                }
+                _ => match backtrack {
+                    Some((star_pos, star_code_pos)) => {
+                        pattern_pos = star_pos + 1;
+                        code_pos = star_code_pos + 1;
+                        backtrack = Some((star_pos, star_code_pos + 1));
+                    }
+                    None => return false,
+                },
+            }
+        }
+
+        self.0[pattern_pos..]
+            .iter()
+            .all(|element| *element == CodePatternElement::AnyRadicals)
+    }
+
+    /// Returns the radicals the pattern starts with, before any wildcard.
+    #[cfg(any(feature = "sqlite", feature = "embedded-data"))]
+    pub(crate) fn literal_prefix(&self) -> CangjieCode {
+        self.iter()
+            .map_while(|element| match *element {
+                CodePatternElement::Radical(radical) => Some(radical),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns a display adapter for printing the pattern in codes.
+    ///
+    /// # Examples
     ///
-    /// [`HowToTypeError::DBError`] if the database query fails.
+    /// ```
+    /// # use libcangjie_howtotype::CodePattern;
+    /// #
+    /// assert_eq!(CodePattern::from_radicals("木*木").codes().to_string(), "d*d");
+    /// ```
+    #[must_use]
+    pub fn codes(&self) -> PatternCodes<'_> {
+        PatternCodes(self)
+    }
+
+    /// Returns a display adapter for printing the pattern in radicals.
     ///
     /// # Examples
     ///
     /// ```
-    /// # use std::error::Error;
+    /// # use libcangjie_howtotype::CodePattern;
     /// #
-    /// # use libcangjie_howtotype::{
-    /// #     CangjieCode, CangjieVersion, LibCangjieHowToType
-    /// # };
+    /// assert_eq!(CodePattern::from_codes(b"d*d").radicals().to_string(), "木*木");
+    /// ```
+    #[must_use]
+    pub fn radicals(&self) -> PatternRadicals<'_> {
+        PatternRadicals {
+            pattern: self,
+            z_radical: ZRadical::default(),
+        }
+    }
+}
+
+impl FromStr for CodePattern {
+    type Err = ParseRadicalError;
+
+    /// Parses a pattern of codes, radicals and wildcards (`*` and `?`).
+    ///
+    /// Each non-wildcard character is parsed as in [`CangjieRadical::try_from`],
+    /// so codes and radicals can be mixed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libcangjie_howtotype::CodePattern;
+    /// #
+    /// assert_eq!("d*d".parse(), Ok(CodePattern::from_codes(b"d*d")));
+    /// assert_eq!("木*木".parse(), Ok(CodePattern::from_codes(b"d*d")));
+    /// assert!("d*1".parse::<CodePattern>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.chars()
+            .enumerate()
+            .map(|(position, c)| match c {
+                '?' => Ok(CodePatternElement::AnyRadical),
+                '*' => Ok(CodePatternElement::AnyRadicals),
+                _ => CangjieRadical::try_from(c)
+                    .map(CodePatternElement::Radical)
+                    .map_err(|error| error.at(position)),
+            })
+            .collect()
+    }
+}
+
+impl Deref for CodePattern {
+    type Target = [CodePatternElement];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<&[CodePatternElement]> for CodePattern {
+    fn from(value: &[CodePatternElement]) -> Self {
+        Self(SmallVec::from(value))
+    }
+}
+
+impl From<&CangjieCode> for CodePattern {
+    fn from(value: &CangjieCode) -> Self {
+        value
+            .iter()
+            .copied()
+            .map(CodePatternElement::Radical)
+            .collect()
+    }
+}
+
+impl FromIterator<CodePatternElement> for CodePattern {
+    fn from_iter<T: IntoIterator<Item = CodePatternElement>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+/// A display adapter for printing a [`CodePattern`] in codes.
+#[derive(Debug)]
+pub struct PatternCodes<'a>(&'a CodePattern);
+
+impl Display for PatternCodes<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for element in self.0.iter() {
+            match element {
+                CodePatternElement::Radical(radical) => {
+                    write!(f, "{}", char::from(radical.to_code()))?;
+                }
+                CodePatternElement::AnyRadical => write!(f, "?")?,
+                CodePatternElement::AnyRadicals => write!(f, "*")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A display adapter for printing a [`CodePattern`] in radicals.
+#[derive(Debug)]
+pub struct PatternRadicals<'a> {
+    pattern: &'a CodePattern,
+    z_radical: ZRadical,
+}
+
+impl PatternRadicals<'_> {
+    /// Chooses how to print the radical for `Z`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libcangjie_howtotype::{CodePattern, ZRadical};
+    /// #
+    /// let pattern = CodePattern::from_codes(b"z*");
+    /// assert_eq!(pattern.radicals().to_string(), "Ｚ*");
+    /// assert_eq!(pattern.radicals().z_radical(ZRadical::Zhong).to_string(), "重*");
+    /// ```
+    #[must_use]
+    pub const fn z_radical(mut self, z_radical: ZRadical) -> Self {
+        self.z_radical = z_radical;
+        self
+    }
+}
+
+impl Display for PatternRadicals<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for element in self.pattern.iter() {
+            match element {
+                CodePatternElement::Radical(radical) => {
+                    write!(f, "{}", radical.to_radical_with(self.z_radical))?;
+                }
+                CodePatternElement::AnyRadical => write!(f, "?")?,
+                CodePatternElement::AnyRadicals => write!(f, "*")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Cangjie version.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum CangjieVersion {
+    V3,
+    V5,
+}
+
+impl CangjieVersion {
+    /// Returns the value used in the `version` column of the database.
+    #[cfg(feature = "sqlite")]
+    const fn to_db_version(self) -> u32 {
+        match self {
+            Self::V3 => schema::DB_VERSION_V3,
+            Self::V5 => schema::DB_VERSION_V5,
+        }
+    }
+}
+
+bitflags! {
+    /// Filter for characters, mirroring libcangjie's `CangjieFilter`.
+    ///
+    /// A character passes the filter if it has any of the selected properties
+    /// (see [`CharacterInfo`]).
+    /// The empty filter is treated as no filtering,
+    /// letting every character pass.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libcangjie_howtotype::CangjieFilter;
+    /// #
+    /// let filter = CangjieFilter::BIG5 | CangjieFilter::HKSCS | CangjieFilter::PUNCTUATION;
+    /// assert!(filter.contains(CangjieFilter::HKSCS));
+    /// assert_eq!(filter.bits(), 0b111);
+    /// ```
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+    #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+    pub struct CangjieFilter: u32 {
+        /// Characters in Big5.
+        const BIG5 = 1 << 0;
+        /// Characters in HKSCS.
+        const HKSCS = 1 << 1;
+        /// Punctuation marks.
+        const PUNCTUATION = 1 << 2;
+        /// Chinese characters.
+        const CHINESE = 1 << 3;
+        /// Zhuyin (Bopomofo) symbols.
+        const ZHUYIN = 1 << 4;
+        /// Japanese kanji.
+        const KANJI = 1 << 5;
+        /// Japanese katakana.
+        const KATAKANA = 1 << 6;
+        /// Japanese hiragana.
+        const HIRAGANA = 1 << 7;
+        /// Symbols.
+        const SYMBOLS = 1 << 8;
+    }
+}
+
+impl CangjieFilter {
+    /// Returns whether a character passes the filter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "sqlite")]
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use libcangjie_howtotype::{CangjieFilter, LibCangjieHowToType};
     /// #
-    /// # fn main() -> Result<(), Box<dyn Error>> {
     /// let cangjie = LibCangjieHowToType::new()?;
     ///
-    /// let how_to_type = cangjie.how_to_type("喵", CangjieVersion::V3)?;
-    /// assert_eq!(*how_to_type, [CangjieCode::from_radicals("口廿田")]);
+    /// let info = cangjie.character_info("喵")?.expect("喵 is in the database");
+    /// assert!(CangjieFilter::CHINESE.matches(&info));
+    /// assert!(!CangjieFilter::HIRAGANA.matches(&info));
+    /// assert!(CangjieFilter::empty().matches(&info));
     /// #
     /// # Ok(())
     /// # }
+    /// # #[cfg(not(feature = "sqlite"))]
+    /// # fn main() {}
     /// ```
-    pub fn how_to_type(
-        &self,
-        character: &str,
-        version: CangjieVersion,
-    ) -> HowToTypeResult<SmallVec<[CangjieCode; 1]>> {
-        let version_num = match version {
-            CangjieVersion::V3 => 3,
-            CangjieVersion::V5 => 5,
-        };
-
-        let mut stmt = self.db_conn.prepare_cached(
-            r"
-                SELECT codes.code
-                FROM chars
-                JOIN codes
-                  ON chars.char_index = codes.char_index
-                WHERE chars.chchar = ?1 AND codes.version = ?2
-            ",
-        )?;
-        let mut rows = stmt.query((character, version_num))?;
-
-        let mut result = SmallVec::new();
-        while let Some(row) = rows.next()? {
-            let ValueRef::Text(code) = row.get_ref_unwrap(0) else {
-                panic!("Unexpected value type")
-            };
-            let code = CangjieCode::from_codes(code);
-
-            result.push(code);
-        }
+    #[must_use]
+    pub fn matches(self, info: &CharacterInfo) -> bool {
+        self.matches_flags(Self::from_info(info))
+    }
 
-        Ok(result)
+    /// Returns whether a character with the given flags passes the filter.
+    pub(crate) fn matches_flags(self, flags: Self) -> bool {
+        self.is_empty() || self.intersects(flags)
+    }
+
+    /// Returns the flags a character has.
+    fn from_info(info: &CharacterInfo) -> Self {
+        [
+            (Self::BIG5, info.big5),
+            (Self::HKSCS, info.hkscs),
+            (Self::PUNCTUATION, info.punctuation),
+            (Self::CHINESE, info.chinese),
+            (Self::ZHUYIN, info.zhuyin),
+            (Self::KANJI, info.kanji),
+            (Self::KATAKANA, info.katakana),
+            (Self::HIRAGANA, info.hiragana),
+            (Self::SYMBOLS, info.symbol),
+        ]
+        .into_iter()
+        .filter(|&(_, has_flag)| has_flag)
+        .map(|(flag, _)| flag)
+        .collect()
     }
 }
 
-/// Error type for [`LibCangjieHowToType::new`].
-#[derive(Debug, Error)]
-#[non_exhaustive]
-pub enum NewError {
-    /// Database error.
-    #[error("Database error")]
-    DBError(#[from] rusqlite::Error),
+/// Error type for parsing radicals and codes.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct ParseRadicalError {
+    character: Option<char>,
+    position: usize,
 }
 
-/// A specialised [`Result`] type for [`LibCangjieHowToType::new`].
-pub type NewResult<T> = Result<T, NewError>;
+impl ParseRadicalError {
+    const fn new(character: char, position: usize) -> Self {
+        Self {
+            character: Some(character),
+            position,
+        }
+    }
 
-/// Error type for [`LibCangjieHowToType::how_to_type`].
-#[derive(Debug, Error)]
-#[non_exhaustive]
-pub enum HowToTypeError {
-    /// Database error.
-    #[error("Database error")]
-    DBError(#[from] rusqlite::Error),
+    /// Creates an error for a radical missing at the end of the input.
+    const fn missing(position: usize) -> Self {
+        Self {
+            character: None,
+            position,
+        }
+    }
+
+    /// Moves the error to the given position.
+    const fn at(self, position: usize) -> Self {
+        Self {
+            character: self.character,
+            position,
+        }
+    }
+
+    /// Returns the character that is not a valid radical or code.
+    ///
+    /// When parsing bytes, this is the offending byte as a [`char`].
+    /// When parsing a single radical from an empty string, this is `'\0'`.
+    #[must_use]
+    pub const fn character(&self) -> char {
+        match self.character {
+            Some(character) => character,
+            None => '\0',
+        }
+    }
+
+    /// Returns the 0-based position of the offending character in the input.
+    ///
+    /// The position is counted in characters for strings
+    /// and in bytes for byte strings.
+    #[must_use]
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl Display for ParseRadicalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.character {
+            Some(character) => write!(
+                f,
+                "Invalid Cangjie radical {character:?} at position {}",
+                self.position,
+            ),
+            None => write!(f, "Missing Cangjie radical at position {}", self.position),
+        }
+    }
 }
 
-/// A specialised [`Result`] type for [`LibCangjieHowToType::how_to_type`].
-pub type HowToTypeResult<T> = Result<T, HowToTypeError>;
+impl Error for ParseRadicalError {}
@@ -1,15 +1,30 @@
+use std::fmt::{self, Display, Formatter};
+use std::path::PathBuf;
 use std::process::ExitCode;
 
-use clap::{Parser, ValueEnum};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use itertools::Itertools;
 
-use libcangjie_howtotype::{CangjieCode, LibCangjieHowToType, NewError};
+use libcangjie_howtotype::{
+    CangjieCode, CangjieFilter, CodePattern, HowToTypeError, HowToTypeResult, LibCangjieHowToType,
+    NewError, ParseRadicalError, ZRadical,
+};
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Parser)]
-#[command(version, about, long_about = None)]
+#[command(
+    version,
+    about,
+    long_about = None,
+    subcommand_negates_reqs = true,
+    override_usage = "libcangjie-howtotype [OPTIONS] <CHARACTER>\n       libcangjie-howtotype [OPTIONS] <COMMAND>",
+)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
     /// The character to query.
-    character: String,
+    #[arg(required = true)]
+    character: Option<String>,
     /// The version of Cangjie used.
     #[arg(
         short = 'C',
@@ -17,17 +32,105 @@ struct Cli {
         value_name = "VERSION",
         default_value_t = CangjieVersion::V3,
         value_enum,
+        global = true,
     )]
     cj_version: CangjieVersion,
     /// The output format.
-    #[arg(short, long, default_value_t = Format::Radical, value_enum)]
+    #[arg(short, long, default_value_t = Format::Radical, value_enum, global = true)]
     format: Format,
-    /// The separator between codes.
-    #[arg(short, long, default_value_t = String::from("\n"))]
+    /// The separator between results.
+    #[arg(short, long, default_value_t = String::from("\n"), global = true)]
     separator: String,
-    /// Do not report an error when the command doesn't know how to type.
+    /// Only consider the characters passing any of the filters, as input methods do.
+    #[arg(long, value_name = "FILTER", value_enum, global = true)]
+    filter: Vec<Filter>,
+    /// Show the candidate position of the character for each code.
     #[arg(short, long)]
+    positions: bool,
+    /// Show the Quick (速成) codes and candidate positions instead.
+    #[arg(long, conflicts_with_all = ["cj_version", "filter", "positions"])]
+    quick: bool,
+    /// Print the radical for Z as "重" instead of "Ｚ".
+    #[arg(long, global = true)]
+    zhong: bool,
+    /// Do not report an error when there is no result.
+    #[arg(short, long, global = true)]
     quiet: bool,
+    /// The path to libcangjie's database.
+    #[arg(long, value_name = "PATH", global = true)]
+    db: Option<PathBuf>,
+}
+
+impl Cli {
+    /// Returns the filter combining all `--filter` options.
+    fn filter(&self) -> CangjieFilter {
+        self.filter
+            .iter()
+            .map(|&filter| CangjieFilter::from(filter))
+            .collect()
+    }
+
+    /// Returns how to print the radical for Z.
+    const fn z_radical(&self) -> ZRadical {
+        if self.zhong {
+            ZRadical::Zhong
+        } else {
+            ZRadical::FullWidthZ
+        }
+    }
+
+    /// Checks the constraints that cannot be expressed with clap's attributes.
+    fn validate(self) -> Result<Self, clap::Error> {
+        if self.command.is_some() && self.character.is_some() {
+            return Err(Self::command().error(
+                ErrorKind::ArgumentConflict,
+                "<CHARACTER> cannot be used with a subcommand",
+            ));
+        }
+
+        if self.command.is_some() && self.quick {
+            return Err(Self::command().error(
+                ErrorKind::ArgumentConflict,
+                "--quick cannot be used with a subcommand",
+            ));
+        }
+
+        if self.command.is_some() && self.positions {
+            return Err(Self::command().error(
+                ErrorKind::ArgumentConflict,
+                "--positions cannot be used with a subcommand",
+            ));
+        }
+
+        Ok(self)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Subcommand)]
+enum Command {
+    /// List the characters that can be typed with a code.
+    Decode {
+        /// The code to query, in either codes (e.g. "rtw") or radicals (e.g. "口廿田").
+        #[arg(value_parser = parse_code)]
+        code: CangjieCode,
+    },
+    /// Search for characters whose code matches a pattern.
+    ///
+    /// In the pattern, "?" matches any single radical,
+    /// and "*" matches any sequence of radicals.
+    Search {
+        /// The pattern to search for, in either codes (e.g. "r*w") or radicals (e.g. "口*田").
+        #[arg(value_parser = parse_pattern)]
+        pattern: CodePattern,
+    },
+    /// Show the information about a character in libcangjie's database.
+    ///
+    /// This shows the flags that decide whether an input method
+    /// lists the character as a candidate, depending on its filters.
+    Info {
+        /// The character to query.
+        character: String,
+    },
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, ValueEnum)]
@@ -55,12 +158,60 @@ enum Format {
     Radical,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, ValueEnum)]
+enum Filter {
+    Big5,
+    Hkscs,
+    Punctuation,
+    Chinese,
+    Zhuyin,
+    Kanji,
+    Katakana,
+    Hiragana,
+    Symbols,
+}
+
+impl From<Filter> for CangjieFilter {
+    fn from(value: Filter) -> Self {
+        match value {
+            Filter::Big5 => Self::BIG5,
+            Filter::Hkscs => Self::HKSCS,
+            Filter::Punctuation => Self::PUNCTUATION,
+            Filter::Chinese => Self::CHINESE,
+            Filter::Zhuyin => Self::ZHUYIN,
+            Filter::Kanji => Self::KANJI,
+            Filter::Katakana => Self::KATAKANA,
+            Filter::Hiragana => Self::HIRAGANA,
+            Filter::Symbols => Self::SYMBOLS,
+        }
+    }
+}
+
+/// Parses a code given in either codes or radicals.
+fn parse_code(s: &str) -> Result<CangjieCode, String> {
+    if s.is_empty() {
+        return Err(String::from("the code is empty"));
+    }
+
+    s.parse().map_err(|e: ParseRadicalError| e.to_string())
+}
+
+/// Parses a code pattern given in either codes or radicals,
+/// possibly containing wildcards.
+fn parse_pattern(s: &str) -> Result<CodePattern, String> {
+    if s.is_empty() {
+        return Err(String::from("the pattern is empty"));
+    }
+
+    s.parse().map_err(|e: ParseRadicalError| e.to_string())
+}
+
 fn main() -> ExitCode {
     human_panic::setup_panic!();
 
     // Reference:
     // https://github.com/crate-ci/typos/blob/master/crates/typos-cli/src/bin/typos-cli/main.rs#L21-L31
-    let args = match Cli::try_parse() {
+    let args = match Cli::try_parse().and_then(Cli::validate) {
         Ok(args) => args,
         Err(e) if e.use_stderr() => {
             let _ = e.print();
@@ -72,7 +223,12 @@ fn main() -> ExitCode {
         }
     };
 
-    let cangjie = match LibCangjieHowToType::new() {
+    let mut builder = LibCangjieHowToType::builder();
+    if let Some(db) = &args.db {
+        builder = builder.path(db);
+    }
+
+    let cangjie = match builder.open() {
         Ok(cangjie) => cangjie,
         Err(NewError::DBError(rusqlite::Error::SqliteFailure(e, _)))
             if matches!(
@@ -92,19 +248,66 @@ fn main() -> ExitCode {
             eprintln!("Error: Cannot open libcangjie's database: {e}");
             return ExitCode::from(u8::try_from(exit_code).expect("Invalid exit code"));
         }
+        Err(NewError::NotFound { tried }) => {
+            eprintln!("Error: Cannot open libcangjie's database: not found in any of:");
+            for path in tried {
+                eprintln!("  {}", path.display());
+            }
+            eprintln!("Set LIBCANGJIE_DB or use --db to specify its location");
+            return ExitCode::from(u8::try_from(exitcode::OSFILE).expect("Invalid exit code"));
+        }
+        Err(e @ NewError::IncompatibleSchema { .. }) => {
+            eprintln!("Error: Cannot open libcangjie's database: {e}");
+            return ExitCode::from(u8::try_from(exitcode::DATAERR).expect("Invalid exit code"));
+        }
         Err(e) => panic!("`LibCangjieHowToType::new` failed: {e}"),
     };
 
-    let how_to_type = cangjie
-        .how_to_type(&args.character, args.cj_version.into())
-        .expect("`LibCangjieHowToType::how_to_type` failed");
+    let result = match args.command {
+        Some(Command::Decode { ref code }) => decode(&cangjie, &args, code),
+        Some(Command::Search { ref pattern }) => search(&cangjie, &args, pattern),
+        Some(Command::Info { ref character }) => info(&cangjie, &args, character),
+        None => {
+            let character = args.character.as_deref().expect("Missing character");
+            how_to_type(&cangjie, &args, character)
+        }
+    };
+
+    match result {
+        Ok(exit_code) => exit_code,
+        Err(
+            e @ (HowToTypeError::UnexpectedValueType { .. }
+            | HowToTypeError::InvalidCode { .. }
+            | HowToTypeError::NotACandidate { .. }),
+        ) => {
+            eprintln!("Error: Unexpected content in libcangjie's database: {e}");
+            ExitCode::from(u8::try_from(exitcode::DATAERR).expect("Invalid exit code"))
+        }
+        Err(e) => panic!("Query failed: {e}"),
+    }
+}
+
+fn how_to_type(
+    cangjie: &LibCangjieHowToType,
+    args: &Cli,
+    character: &str,
+) -> HowToTypeResult<ExitCode> {
+    if args.quick {
+        return how_to_type_quick(cangjie, args, character);
+    }
+    if args.positions {
+        return how_to_type_with_positions(cangjie, args, character);
+    }
+
+    let how_to_type =
+        cangjie.how_to_type_with_filter(character, args.cj_version.into(), args.filter())?;
 
     if how_to_type.is_empty() {
         if args.quiet {
-            return ExitCode::SUCCESS;
+            return Ok(ExitCode::SUCCESS);
         } else {
-            eprintln!("Error: Don't know how to type '{}'", args.character);
-            return ExitCode::FAILURE;
+            eprintln!("Error: Don't know how to type '{character}'");
+            return Ok(ExitCode::FAILURE);
         }
     }
 
@@ -120,12 +323,215 @@ fn main() -> ExitCode {
             "{}",
             how_to_type
                 .iter()
-                .map(CangjieCode::radicals)
+                .map(|code| code.radicals().z_radical(args.z_radical()))
+                .format(&args.separator),
+        ),
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn how_to_type_with_positions(
+    cangjie: &LibCangjieHowToType,
+    args: &Cli,
+    character: &str,
+) -> HowToTypeResult<ExitCode> {
+    let how_to_type =
+        cangjie.how_to_type_with_positions(character, args.cj_version.into(), args.filter())?;
+
+    if how_to_type.is_empty() {
+        if args.quiet {
+            return Ok(ExitCode::SUCCESS);
+        } else {
+            eprintln!("Error: Don't know how to type '{character}'");
+            return Ok(ExitCode::FAILURE);
+        }
+    }
+
+    match args.format {
+        Format::Code => println!(
+            "{}",
+            how_to_type
+                .iter()
+                .map(|candidate_code| format!(
+                    "{} ({} candidate)",
+                    candidate_code.code.codes(),
+                    Ordinal(candidate_code.position),
+                ))
+                .format(&args.separator),
+        ),
+        Format::Radical => println!(
+            "{}",
+            how_to_type
+                .iter()
+                .map(|candidate_code| format!(
+                    "{} ({} candidate)",
+                    candidate_code.code.radicals().z_radical(args.z_radical()),
+                    Ordinal(candidate_code.position),
+                ))
                 .format(&args.separator),
         ),
     }
 
-    ExitCode::SUCCESS
+    Ok(ExitCode::SUCCESS)
+}
+
+fn how_to_type_quick(
+    cangjie: &LibCangjieHowToType,
+    args: &Cli,
+    character: &str,
+) -> HowToTypeResult<ExitCode> {
+    let how_to_type = cangjie.how_to_type_quick(character)?;
+
+    if how_to_type.is_empty() {
+        if args.quiet {
+            return Ok(ExitCode::SUCCESS);
+        } else {
+            eprintln!("Error: Don't know how to type '{character}'");
+            return Ok(ExitCode::FAILURE);
+        }
+    }
+
+    match args.format {
+        Format::Code => println!(
+            "{}",
+            how_to_type
+                .iter()
+                .map(|quick_code| format!(
+                    "{} ({} candidate)",
+                    quick_code.code.codes(),
+                    Ordinal(quick_code.position),
+                ))
+                .format(&args.separator),
+        ),
+        Format::Radical => println!(
+            "{}",
+            how_to_type
+                .iter()
+                .map(|quick_code| format!(
+                    "{} ({} candidate)",
+                    quick_code.code.radicals().z_radical(args.z_radical()),
+                    Ordinal(quick_code.position),
+                ))
+                .format(&args.separator),
+        ),
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn decode(
+    cangjie: &LibCangjieHowToType,
+    args: &Cli,
+    code: &CangjieCode,
+) -> HowToTypeResult<ExitCode> {
+    let characters =
+        cangjie.characters_for_with_filter(code, args.cj_version.into(), args.filter())?;
+
+    if characters.is_empty() {
+        if args.quiet {
+            return Ok(ExitCode::SUCCESS);
+        } else {
+            eprintln!(
+                "Error: No character can be typed with '{}'",
+                code.radicals().z_radical(args.z_radical()),
+            );
+            return Ok(ExitCode::FAILURE);
+        }
+    }
+
+    println!("{}", characters.iter().format(&args.separator));
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn search(
+    cangjie: &LibCangjieHowToType,
+    args: &Cli,
+    pattern: &CodePattern,
+) -> HowToTypeResult<ExitCode> {
+    let results = cangjie.search_with_filter(pattern, args.cj_version.into(), args.filter())?;
+
+    if results.is_empty() {
+        if args.quiet {
+            return Ok(ExitCode::SUCCESS);
+        } else {
+            eprintln!(
+                "Error: No character has a code matching '{}'",
+                pattern.radicals().z_radical(args.z_radical()),
+            );
+            return Ok(ExitCode::FAILURE);
+        }
+    }
+
+    match args.format {
+        Format::Code => println!(
+            "{}",
+            results
+                .iter()
+                .map(|(character, code)| format!("{character} {}", code.codes()))
+                .format(&args.separator),
+        ),
+        Format::Radical => println!(
+            "{}",
+            results
+                .iter()
+                .map(|(character, code)| format!(
+                    "{character} {}",
+                    code.radicals().z_radical(args.z_radical())
+                ))
+                .format(&args.separator),
+        ),
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn info(cangjie: &LibCangjieHowToType, args: &Cli, character: &str) -> HowToTypeResult<ExitCode> {
+    let Some(info) = cangjie.character_info(character)? else {
+        if args.quiet {
+            return Ok(ExitCode::SUCCESS);
+        } else {
+            eprintln!("Error: '{character}' is not in libcangjie's database");
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+
+    let yes_no = |flag| if flag { "yes" } else { "no" };
+
+    println!("Character: {}", info.character);
+    if let Some(simplified) = &info.simplified {
+        println!("Simplified: {simplified}");
+    }
+    println!("Chinese: {}", yes_no(info.chinese));
+    println!("Big5: {}", yes_no(info.big5));
+    println!("HKSCS: {}", yes_no(info.hkscs));
+    println!("Zhuyin: {}", yes_no(info.zhuyin));
+    println!("Kanji: {}", yes_no(info.kanji));
+    println!("Hiragana: {}", yes_no(info.hiragana));
+    println!("Katakana: {}", yes_no(info.katakana));
+    println!("Punctuation: {}", yes_no(info.punctuation));
+    println!("Symbol: {}", yes_no(info.symbol));
+    println!("Frequency: {}", info.frequency);
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// A display adapter for printing an English ordinal number (1st, 2nd, …).
+struct Ordinal(usize);
+
+impl Display for Ordinal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let suffix = match (self.0 % 10, self.0 % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        };
+
+        write!(f, "{}{suffix}", self.0)
+    }
 }
 
 #[cfg(test)]
@@ -134,7 +540,19 @@ mod tests {
 
     #[test]
     fn verify_cli() {
-        use clap::CommandFactory;
         Cli::command().debug_assert();
     }
+
+    #[test]
+    fn ordinal() {
+        assert_eq!(Ordinal(1).to_string(), "1st");
+        assert_eq!(Ordinal(2).to_string(), "2nd");
+        assert_eq!(Ordinal(3).to_string(), "3rd");
+        assert_eq!(Ordinal(4).to_string(), "4th");
+        assert_eq!(Ordinal(11).to_string(), "11th");
+        assert_eq!(Ordinal(12).to_string(), "12th");
+        assert_eq!(Ordinal(13).to_string(), "13th");
+        assert_eq!(Ordinal(21).to_string(), "21st");
+        assert_eq!(Ordinal(112).to_string(), "112th");
+    }
 }
@@ -0,0 +1,299 @@
+//! The in-memory backend, holding the whole database in memory.
+
+use std::collections::HashMap;
+
+use smallvec::SmallVec;
+
+use crate::{
+    CangjieCode, CangjieDatabase, CangjieFilter, CangjieRadical, CangjieVersion, CharacterInfo,
+    CodePattern, HowToTypeResult, LibCangjieHowToType, SqliteDatabase,
+};
+
+/// The backend holding the whole database in memory.
+///
+/// The characters are kept in hash maps and the codes in a sorted index,
+/// so that queries do not touch the database at all.
+/// Loading takes a while and some memory,
+/// which pays off when many queries are made.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # use libcangjie_howtotype::{
+/// #     CangjieCode, CangjieVersion, LibCangjieHowToType
+/// # };
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let cangjie = LibCangjieHowToType::new()?.to_in_memory()?;
+///
+/// let how_to_type = cangjie.how_to_type("喵", CangjieVersion::V3)?;
+/// assert_eq!(*how_to_type, [CangjieCode::from_radicals("口廿田")]);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct InMemoryCangjie {
+    /// All characters, in candidate order.
+    characters: Vec<CharacterInfo>,
+    /// The positions in `characters`, by character.
+    positions: HashMap<String, usize>,
+    v3: CodeIndex,
+    v5: CodeIndex,
+}
+
+impl InMemoryCangjie {
+    /// Loads the whole database from the SQLite backend.
+    ///
+    /// # Errors
+    ///
+    /// - [`HowToTypeError::DBError`](crate::HowToTypeError::DBError)
+    ///   if the database query fails.
+    /// - [`HowToTypeError::UnexpectedValueType`](crate::HowToTypeError::UnexpectedValueType)
+    ///   or [`HowToTypeError::InvalidCode`](crate::HowToTypeError::InvalidCode)
+    ///   if the database contains unexpected data.
+    pub fn from_sqlite(database: &SqliteDatabase) -> HowToTypeResult<Self> {
+        let mut characters = Vec::new();
+        let mut char_indices = HashMap::new();
+        for (char_index, info) in database.all_characters()? {
+            char_indices.insert(char_index, characters.len());
+            characters.push(info);
+        }
+
+        let load = |version| -> HowToTypeResult<Vec<(usize, CangjieCode)>> {
+            let codes = database
+                .all_codes(version)?
+                .into_iter()
+                .filter_map(|(char_index, code)| {
+                    char_indices
+                        .get(&char_index)
+                        .map(|&position| (position, code))
+                })
+                .collect();
+
+            Ok(codes)
+        };
+        let v3 = load(CangjieVersion::V3)?;
+        let v5 = load(CangjieVersion::V5)?;
+
+        Ok(Self::from_parts(characters, v3, v5))
+    }
+
+    /// Builds the indices from the characters in candidate order
+    /// and the `(position, code)` pairs of each version.
+    fn from_parts(
+        characters: Vec<CharacterInfo>,
+        v3: impl IntoIterator<Item = (usize, CangjieCode)>,
+        v5: impl IntoIterator<Item = (usize, CangjieCode)>,
+    ) -> Self {
+        let positions = characters
+            .iter()
+            .enumerate()
+            .map(|(position, info)| (info.character.clone(), position))
+            .collect();
+        let v3 = CodeIndex::new(characters.len(), v3);
+        let v5 = CodeIndex::new(characters.len(), v5);
+
+        Self {
+            characters,
+            positions,
+            v3,
+            v5,
+        }
+    }
+
+    /// Returns the code index of a version.
+    const fn index(&self, version: CangjieVersion) -> &CodeIndex {
+        match version {
+            CangjieVersion::V3 => &self.v3,
+            CangjieVersion::V5 => &self.v5,
+        }
+    }
+
+    /// Iterates over the codes matching a pattern
+    /// together with the positions of their characters passing the filter,
+    /// ordered by code and then in candidate order.
+    fn matching<'a>(
+        &'a self,
+        pattern: &'a CodePattern,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> impl Iterator<Item = &'a (CangjieCode, usize)> {
+        self.index(version)
+            .starting_with(&pattern.literal_prefix())
+            .iter()
+            .filter(move |(code, position)| {
+                pattern.matches(code) && filter.matches(&self.characters[*position])
+            })
+    }
+}
+
+impl CangjieDatabase for InMemoryCangjie {
+    fn codes_for_character(
+        &self,
+        character: &str,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<SmallVec<[CangjieCode; 1]>> {
+        let codes = self
+            .positions
+            .get(character)
+            .filter(|&&position| filter.matches(&self.characters[position]))
+            .map(|&position| self.index(version).by_character[position].clone())
+            .unwrap_or_default();
+
+        Ok(codes)
+    }
+
+    fn characters_for_code(
+        &self,
+        code: &CangjieCode,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<String>> {
+        let characters = self
+            .index(version)
+            .starting_with(code)
+            .iter()
+            .take_while(|(candidate, _)| candidate == code)
+            .map(|&(_, position)| &self.characters[position])
+            .filter(|info| filter.matches(info))
+            .map(|info| info.character.clone())
+            .collect();
+
+        Ok(characters)
+    }
+
+    fn entries(
+        &self,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<(String, CangjieCode)>> {
+        let index = self.index(version);
+        let entries = self
+            .characters
+            .iter()
+            .zip(&index.by_character)
+            .filter(|(info, _)| filter.matches(info))
+            .flat_map(|(info, codes)| {
+                codes
+                    .iter()
+                    .map(|code| (info.character.clone(), code.clone()))
+            })
+            .collect();
+
+        Ok(entries)
+    }
+
+    fn entries_with_flags(
+        &self,
+        version: CangjieVersion,
+    ) -> HowToTypeResult<Vec<(String, CangjieCode, CangjieFilter)>> {
+        let index = self.index(version);
+        let entries = self
+            .characters
+            .iter()
+            .zip(&index.by_character)
+            .flat_map(|(info, codes)| {
+                let flags = CangjieFilter::from_info(info);
+                codes
+                    .iter()
+                    .map(move |code| (info.character.clone(), code.clone(), flags))
+            })
+            .collect();
+
+        Ok(entries)
+    }
+
+    fn character_info(&self, character: &str) -> HowToTypeResult<Option<CharacterInfo>> {
+        Ok(self
+            .positions
+            .get(character)
+            .map(|&position| self.characters[position].clone()))
+    }
+
+    fn search(
+        &self,
+        pattern: &CodePattern,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<(String, CangjieCode)>> {
+        let results = self
+            .matching(pattern, version, filter)
+            .map(|(code, position)| (self.characters[*position].character.clone(), code.clone()))
+            .collect();
+
+        Ok(results)
+    }
+
+    fn characters_matching(
+        &self,
+        pattern: &CodePattern,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<String>> {
+        let mut positions = self
+            .matching(pattern, version, filter)
+            .map(|&(_, position)| position)
+            .collect::<Vec<_>>();
+        positions.sort_unstable();
+        positions.dedup();
+
+        Ok(positions
+            .into_iter()
+            .map(|position| self.characters[position].character.clone())
+            .collect())
+    }
+}
+
+impl LibCangjieHowToType {
+    /// Loads the whole database into memory,
+    /// creating a `LibCangjieHowToType` backed by [`InMemoryCangjie`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`InMemoryCangjie::from_sqlite`].
+    pub fn to_in_memory(&self) -> HowToTypeResult<LibCangjieHowToType<InMemoryCangjie>> {
+        InMemoryCangjie::from_sqlite(self.database()).map(LibCangjieHowToType::from_database)
+    }
+}
+
+/// The codes of one Cangjie version.
+#[derive(Debug, Clone)]
+struct CodeIndex {
+    /// The codes of each character, by position in candidate order.
+    by_character: Vec<SmallVec<[CangjieCode; 1]>>,
+    /// All codes with the positions of their characters,
+    /// sorted by code and then by position.
+    by_code: Vec<(CangjieCode, usize)>,
+}
+
+impl CodeIndex {
+    /// Builds the index for `len` characters from `(position, code)` pairs.
+    ///
+    /// The codes of each character keep the order they are given in.
+    fn new(len: usize, codes: impl IntoIterator<Item = (usize, CangjieCode)>) -> Self {
+        let mut by_character = vec![SmallVec::new(); len];
+        let mut by_code = Vec::new();
+        for (position, code) in codes {
+            by_character[position].push(code.clone());
+            by_code.push((code, position));
+        }
+        by_code.sort_unstable();
+
+        Self {
+            by_character,
+            by_code,
+        }
+    }
+
+    /// Returns the part of `by_code` whose codes start with a prefix.
+    fn starting_with(&self, prefix: &[CangjieRadical]) -> &[(CangjieCode, usize)] {
+        let start = self.by_code.partition_point(|(code, _)| **code < *prefix);
+        let len = self.by_code[start..].partition_point(|(code, _)| code.starts_with(prefix));
+
+        &self.by_code[start..start + len]
+    }
+}
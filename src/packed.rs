@@ -0,0 +1,309 @@
+//! The packed integer representation of codes.
+
+use core::error::Error;
+use core::fmt::{self, Display, Formatter};
+use core::iter::FusedIterator;
+use core::ops::RangeInclusive;
+
+use crate::{CangjieCode, CangjieRadical};
+
+/// Code packed into a [`u32`], for storing many codes compactly.
+///
+/// A packed code holds up to [`MAX_LEN`](Self::MAX_LEN) radicals,
+/// which is enough for every code in libcangjie's database.
+/// It converts losslessly to and from [`CangjieCode`],
+/// and orders the same way as [`CangjieCode`],
+/// so that a sorted list of packed codes can be searched by prefix
+/// with [`prefix_range`](Self::prefix_range).
+///
+/// The radicals take 5 bits each, from the most significant end,
+/// as their index in the alphabet plus one, with 0 for no radical.
+/// The lowest 3 bits are the length.
+///
+/// # Examples
+///
+/// ```
+/// # use libcangjie_howtotype::{CangjieCode, PackedCangjieCode};
+/// #
+/// let code = CangjieCode::from_codes(b"rtw");
+/// let packed = PackedCangjieCode::try_from(&code).unwrap();
+///
+/// assert_eq!(packed.len(), 3);
+/// assert_eq!(CangjieCode::from(packed), code);
+/// assert!(packed < PackedCangjieCode::try_from(&CangjieCode::from_codes(b"s")).unwrap());
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct PackedCangjieCode(u32);
+
+impl PackedCangjieCode {
+    /// The maximum number of radicals in a packed code.
+    pub const MAX_LEN: usize = 5;
+
+    /// The number of bits taken by each radical.
+    const RADICAL_BITS: u32 = 5;
+
+    /// The number of bits taken by the length.
+    const LEN_BITS: u32 = 3;
+
+    /// Packs the radicals of a code,
+    /// returning `None` if there are more than [`MAX_LEN`](Self::MAX_LEN).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libcangjie_howtotype::{CangjieRadical, PackedCangjieCode};
+    /// #
+    /// assert!(PackedCangjieCode::new(&[CangjieRadical::R, CangjieRadical::T]).is_some());
+    /// assert!(PackedCangjieCode::new(&[CangjieRadical::A; 6]).is_none());
+    /// ```
+    #[must_use]
+    pub const fn new(radicals: &[CangjieRadical]) -> Option<Self> {
+        if radicals.len() > Self::MAX_LEN {
+            return None;
+        }
+
+        let mut bits = radicals.len() as u32;
+        let mut index = 0;
+        while index < radicals.len() {
+            bits |= (radicals[index] as u32 + 1) << Self::shift(index);
+            index += 1;
+        }
+
+        Some(Self(bits))
+    }
+
+    /// Returns the packed code with the given bits,
+    /// as returned by [`to_bits`](Self::to_bits),
+    /// or `None` if the bits are not a valid packed code.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libcangjie_howtotype::{CangjieCode, PackedCangjieCode};
+    /// #
+    /// let packed = PackedCangjieCode::try_from(&CangjieCode::from_codes(b"rtw")).unwrap();
+    /// assert_eq!(PackedCangjieCode::from_bits(packed.to_bits()), Some(packed));
+    /// assert_eq!(PackedCangjieCode::from_bits(u32::MAX), None);
+    /// ```
+    #[must_use]
+    pub const fn from_bits(bits: u32) -> Option<Self> {
+        let packed = Self(bits);
+        let len = packed.len();
+        if len > Self::MAX_LEN || bits >> Self::shift(0) >> Self::RADICAL_BITS != 0 {
+            return None;
+        }
+
+        let mut index = 0;
+        while index < Self::MAX_LEN {
+            let slot = packed.slot(index);
+            if (index < len && (slot == 0 || slot > 26)) || (index >= len && slot != 0) {
+                return None;
+            }
+            index += 1;
+        }
+
+        Some(packed)
+    }
+
+    /// Returns the bits of the packed code.
+    #[must_use]
+    pub const fn to_bits(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the number of radicals.
+    #[must_use]
+    pub const fn len(self) -> usize {
+        (self.0 & ((1 << Self::LEN_BITS) - 1)) as usize
+    }
+
+    /// Returns whether the code has no radicals.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the radical at an index, or `None` if out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libcangjie_howtotype::{CangjieCode, CangjieRadical, PackedCangjieCode};
+    /// #
+    /// let packed = PackedCangjieCode::try_from(&CangjieCode::from_codes(b"rtw")).unwrap();
+    /// assert_eq!(packed.get(1), Some(CangjieRadical::T));
+    /// assert_eq!(packed.get(3), None);
+    /// ```
+    #[must_use]
+    pub const fn get(self, index: usize) -> Option<CangjieRadical> {
+        if index >= self.len() {
+            return None;
+        }
+
+        Some(CangjieRadical::from_code(b'a' + self.slot(index) as u8 - 1))
+    }
+
+    /// Returns an iterator over the radicals.
+    #[must_use]
+    pub const fn iter(self) -> PackedRadicals {
+        PackedRadicals {
+            code: self,
+            index: 0,
+        }
+    }
+
+    /// Returns whether the code starts with a prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libcangjie_howtotype::{CangjieCode, PackedCangjieCode};
+    /// #
+    /// let packed = |codes| PackedCangjieCode::try_from(&CangjieCode::from_codes(codes)).unwrap();
+    /// assert!(packed(b"rtw").starts_with(packed(b"rt")));
+    /// assert!(packed(b"rtw").starts_with(packed(b"")));
+    /// assert!(!packed(b"rtw").starts_with(packed(b"rw")));
+    /// ```
+    #[must_use]
+    pub const fn starts_with(self, prefix: Self) -> bool {
+        let len = prefix.len();
+        len <= self.len() && self.radical_bits(len) == prefix.radical_bits(len)
+    }
+
+    /// Returns the range of the codes starting with this code as a prefix.
+    ///
+    /// Since packed codes order the same way as codes,
+    /// this is a contiguous range,
+    /// which can be used to search a sorted list or a `BTreeMap`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use libcangjie_howtotype::{CangjieCode, PackedCangjieCode};
+    /// #
+    /// let packed = |codes| PackedCangjieCode::try_from(&CangjieCode::from_codes(codes)).unwrap();
+    ///
+    /// let mut codes = [packed(b"a"), packed(b"rtw"), packed(b"r"), packed(b"rzzzz"), packed(b"s")];
+    /// codes.sort_unstable();
+    ///
+    /// let range = packed(b"r").prefix_range();
+    /// let start = codes.partition_point(|code| code < range.start());
+    /// let end = codes.partition_point(|code| code <= range.end());
+    /// assert_eq!(codes[start..end], [packed(b"r"), packed(b"rtw"), packed(b"rzzzz")]);
+    /// ```
+    #[must_use]
+    pub const fn prefix_range(self) -> RangeInclusive<Self> {
+        let len = self.len();
+        let mut last = self.radical_bits(len) | Self::MAX_LEN as u32;
+        let mut index = len;
+        while index < Self::MAX_LEN {
+            last |= 26 << Self::shift(index);
+            index += 1;
+        }
+
+        RangeInclusive::new(self, Self(last))
+    }
+
+    /// Returns the shift of the radical at an index.
+    const fn shift(index: usize) -> u32 {
+        Self::LEN_BITS + Self::RADICAL_BITS * (Self::MAX_LEN - 1 - index) as u32
+    }
+
+    /// Returns the slot at an index, 0 if there is no radical.
+    const fn slot(self, index: usize) -> u32 {
+        (self.0 >> Self::shift(index)) & ((1 << Self::RADICAL_BITS) - 1)
+    }
+
+    /// Returns the bits of the first `len` radicals.
+    const fn radical_bits(self, len: usize) -> u32 {
+        if len == 0 {
+            return 0;
+        }
+
+        self.0 >> Self::shift(len - 1) << Self::shift(len - 1)
+    }
+}
+
+impl TryFrom<&[CangjieRadical]> for PackedCangjieCode {
+    type Error = CodeTooLongError;
+
+    fn try_from(value: &[CangjieRadical]) -> Result<Self, Self::Error> {
+        Self::new(value).ok_or(CodeTooLongError { len: value.len() })
+    }
+}
+
+impl TryFrom<&CangjieCode> for PackedCangjieCode {
+    type Error = CodeTooLongError;
+
+    fn try_from(value: &CangjieCode) -> Result<Self, Self::Error> {
+        Self::try_from(&**value)
+    }
+}
+
+impl From<PackedCangjieCode> for CangjieCode {
+    fn from(value: PackedCangjieCode) -> Self {
+        value.iter().collect()
+    }
+}
+
+impl IntoIterator for PackedCangjieCode {
+    type Item = CangjieRadical;
+    type IntoIter = PackedRadicals;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the radicals of a [`PackedCangjieCode`].
+#[derive(Debug, Clone)]
+pub struct PackedRadicals {
+    code: PackedCangjieCode,
+    index: usize,
+}
+
+impl Iterator for PackedRadicals {
+    type Item = CangjieRadical;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let radical = self.code.get(self.index)?;
+        self.index += 1;
+        Some(radical)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.code.len() - self.index;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for PackedRadicals {}
+
+impl FusedIterator for PackedRadicals {}
+
+/// Error type for packing a code longer than [`PackedCangjieCode::MAX_LEN`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct CodeTooLongError {
+    len: usize,
+}
+
+impl CodeTooLongError {
+    /// Returns the length of the code.
+    #[must_use]
+    pub const fn code_len(&self) -> usize {
+        self.len
+    }
+}
+
+impl Display for CodeTooLongError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Cangjie code of {} radicals is longer than {}",
+            self.len,
+            PackedCangjieCode::MAX_LEN,
+        )
+    }
+}
+
+impl Error for CodeTooLongError {}
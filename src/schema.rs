@@ -0,0 +1,33 @@
+//! The parts of libcangjie's database schema read by this crate.
+//!
+//! This module is also included by `build.rs` for the `embedded-data` feature,
+//! so it must not depend on the rest of the crate.
+
+/// The tables of libcangjie's database and the columns read from each of them.
+pub(crate) const EXPECTED_COLUMNS: [(&str, &[&str]); 2] = [
+    (
+        "chars",
+        &[
+            "char_index",
+            "chchar",
+            "simpchar",
+            "zh",
+            "big5",
+            "hkscs",
+            "zhuyin",
+            "kanji",
+            "hiragana",
+            "katakana",
+            "punct",
+            "symbol",
+            "frequency",
+        ],
+    ),
+    ("codes", &["char_index", "version", "code"]),
+];
+
+/// The value of `codes.version` for Cangjie 3.
+pub(crate) const DB_VERSION_V3: u32 = 3;
+
+/// The value of `codes.version` for Cangjie 5.
+pub(crate) const DB_VERSION_V5: u32 = 5;
@@ -0,0 +1,202 @@
+//! Serde support, with the `serde` feature.
+//!
+//! By default, [`CangjieRadical`], [`CangjieCode`] and [`CodePattern`]
+//! are serialised as strings of codes (e.g. `"rtw"`),
+//! and [`CangjieVersion`] as its number (e.g. `3`).
+//! When deserialising, codes and radicals are both accepted,
+//! as in [`FromStr`].
+//!
+//! To serialise a [`CangjieCode`] as radicals (e.g. `"口廿田"`) instead,
+//! use [`radicals`] with `#[serde(with = "...")]`.
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::error::Error;
+//! #
+//! # use libcangjie_howtotype::CangjieCode;
+//! # use serde::{Deserialize, Serialize};
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! #[derive(Serialize, Deserialize)]
+//! struct Entry {
+//!     code: CangjieCode,
+//!     #[serde(with = "libcangjie_howtotype::serde::radicals")]
+//!     radicals: CangjieCode,
+//! }
+//!
+//! let entry = Entry {
+//!     code: CangjieCode::from_codes(b"rtw"),
+//!     radicals: CangjieCode::from_codes(b"rtw"),
+//! };
+//! assert_eq!(
+//!     serde_json::to_string(&entry)?,
+//!     r#"{"code":"rtw","radicals":"口廿田"}"#,
+//! );
+//! #
+//! # Ok(())
+//! # }
+//! ```
+
+use core::fmt::{self, Formatter};
+use core::marker::PhantomData;
+use core::str::FromStr;
+
+use ::serde::de::{self, Deserializer, Unexpected, Visitor};
+use ::serde::{Deserialize, Serialize, Serializer};
+
+use crate::{CangjieCode, CangjieRadical, CangjieVersion, CodePattern};
+
+impl Serialize for CangjieRadical {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(char::from(self.to_code()))
+    }
+}
+
+impl<'de> Deserialize<'de> for CangjieRadical {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let c = char::deserialize(deserializer)?;
+        Self::try_from(c).map_err(|_| {
+            de::Error::invalid_value(Unexpected::Char(c), &"a Cangjie code or radical")
+        })
+    }
+}
+
+impl Serialize for CangjieCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.codes())
+    }
+}
+
+impl<'de> Deserialize<'de> for CangjieCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(FromStrVisitor::new("a Cangjie code in codes or radicals"))
+    }
+}
+
+impl Serialize for CodePattern {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.codes())
+    }
+}
+
+impl<'de> Deserialize<'de> for CodePattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(FromStrVisitor::new(
+            "a Cangjie code pattern in codes or radicals",
+        ))
+    }
+}
+
+impl Serialize for CangjieVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let version: u8 = match self {
+            Self::V3 => 3,
+            Self::V5 => 5,
+        };
+        serializer.serialize_u8(version)
+    }
+}
+
+impl<'de> Deserialize<'de> for CangjieVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match u8::deserialize(deserializer)? {
+            3 => Ok(Self::V3),
+            5 => Ok(Self::V5),
+            version => Err(de::Error::invalid_value(
+                Unexpected::Unsigned(version.into()),
+                &"3 or 5",
+            )),
+        }
+    }
+}
+
+/// Serialises a [`CangjieCode`] as a string of radicals (e.g. `"口廿田"`).
+///
+/// Use with `#[serde(with = "libcangjie_howtotype::serde::radicals")]`.
+/// Deserialisation accepts both codes and radicals.
+pub mod radicals {
+    use ::serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::CangjieCode;
+
+    /// Serialises a code as radicals.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the serialiser.
+    pub fn serialize<S: Serializer>(code: &CangjieCode, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&code.radicals())
+    }
+
+    /// Deserialises a code in either codes or radicals.
+    ///
+    /// # Errors
+    ///
+    /// If the input is not a string of codes or radicals,
+    /// or any error from the deserialiser.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<CangjieCode, D::Error> {
+        CangjieCode::deserialize(deserializer)
+    }
+}
+
+/// Serialises a [`CangjieCode`] as a string of codes (e.g. `"rtw"`).
+///
+/// This is the default representation,
+/// provided for symmetry with [`radicals`].
+pub mod codes {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::CangjieCode;
+
+    /// Serialises a code as codes.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the serialiser.
+    pub fn serialize<S: Serializer>(code: &CangjieCode, serializer: S) -> Result<S::Ok, S::Error> {
+        code.serialize(serializer)
+    }
+
+    /// Deserialises a code in either codes or radicals.
+    ///
+    /// # Errors
+    ///
+    /// If the input is not a string of codes or radicals,
+    /// or any error from the deserialiser.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<CangjieCode, D::Error> {
+        CangjieCode::deserialize(deserializer)
+    }
+}
+
+/// Visitor parsing a string with [`FromStr`].
+struct FromStrVisitor<T> {
+    expecting: &'static str,
+    marker: PhantomData<T>,
+}
+
+impl<T> FromStrVisitor<T> {
+    const fn new(expecting: &'static str) -> Self {
+        Self {
+            expecting,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: FromStr> Visitor<'_> for FromStrVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse()
+            .map_err(|_| E::invalid_value(Unexpected::Str(v), &self))
+    }
+}
@@ -0,0 +1,595 @@
+//! The SQLite backend, reading libcangjie's database.
+
+use std::fmt::{self, Debug, Formatter};
+use std::path::Path;
+use std::str;
+
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, MAIN_DB, OpenFlags, Row};
+use smallvec::SmallVec;
+
+use crate::schema::EXPECTED_COLUMNS;
+use crate::{
+    CangjieCode, CangjieDatabase, CangjieFilter, CangjieRadical, CangjieVersion, CharacterInfo,
+    CodePattern, HowToTypeError, HowToTypeResult, NewError, NewResult, SchemaGeneration,
+};
+
+/// SQL condition for [`CangjieFilter`],
+/// expecting the filter bits as the parameter `?3`.
+macro_rules! filter_condition {
+    () => {
+        r"
+            (
+                ?3 = 0
+                OR (?3 & 1 != 0 AND chars.big5 != 0)
+                OR (?3 & 2 != 0 AND chars.hkscs != 0)
+                OR (?3 & 4 != 0 AND chars.punct != 0)
+                OR (?3 & 8 != 0 AND chars.zh != 0)
+                OR (?3 & 16 != 0 AND chars.zhuyin != 0)
+                OR (?3 & 32 != 0 AND chars.kanji != 0)
+                OR (?3 & 64 != 0 AND chars.katakana != 0)
+                OR (?3 & 128 != 0 AND chars.hiragana != 0)
+                OR (?3 & 256 != 0 AND chars.symbol != 0)
+            )
+        "
+    };
+}
+
+/// The columns of `chars` read by [`read_character_info`], in order.
+macro_rules! character_info_columns {
+    () => {
+        r"
+            char_index, chchar, simpchar,
+            zh, big5, hkscs, zhuyin, kanji, hiragana, katakana, punct, symbol,
+            frequency
+        "
+    };
+}
+
+/// The backend reading libcangjie's SQLite database.
+///
+/// This is the default backend of [`LibCangjieHowToType`](crate::LibCangjieHowToType).
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # use libcangjie_howtotype::{CangjieDatabase, CangjieFilter, CangjieVersion, SqliteDatabase};
+/// # use libcangjie_howtotype::rusqlite::{Connection, OpenFlags};
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let db_conn = Connection::open_with_flags(
+///     "/usr/share/libcangjie/cangjie.db",
+///     OpenFlags::SQLITE_OPEN_READ_ONLY,
+/// )?;
+/// let database = SqliteDatabase::from_connection(db_conn);
+///
+/// let codes =
+///     database.codes_for_character("喵", CangjieVersion::V3, CangjieFilter::empty())?;
+/// assert_eq!(codes.len(), 1);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SqliteDatabase {
+    db_conn: Connection,
+}
+
+/// The SQLCipher settings to open a database with.
+///
+/// Without a key, the database is opened as a plain SQLite database.
+#[derive(PartialEq, Eq, Clone, Hash, Default)]
+pub(crate) struct CipherSettings {
+    /// The key, passed to `PRAGMA key`.
+    pub(crate) key: Option<String>,
+    /// The value of `PRAGMA cipher_compatibility`, if any.
+    pub(crate) compatibility: Option<u8>,
+}
+
+impl Debug for CipherSettings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Keep the key out of logs.
+        f.debug_struct("CipherSettings")
+            .field("key", &self.key.as_ref().map(|_| ".."))
+            .field("compatibility", &self.compatibility)
+            .finish()
+    }
+}
+
+impl SqliteDatabase {
+    /// Creates a new `SqliteDatabase` from an existing database connection.
+    ///
+    /// The connection should point to a database with libcangjie's schema.
+    #[must_use]
+    pub const fn from_connection(db_conn: Connection) -> Self {
+        Self { db_conn }
+    }
+
+    /// Opens a read-only connection to a database.
+    ///
+    /// If a key is given, the key is checked by reading the schema,
+    /// which fails with [`ErrorCode::NotADatabase`](rusqlite::ffi::ErrorCode::NotADatabase)
+    /// if the key is wrong.
+    pub(crate) fn open(path: &Path, cipher: &CipherSettings) -> rusqlite::Result<Self> {
+        let db_conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+
+        if let Some(key) = &cipher.key {
+            db_conn.pragma_update(None, "key", key)?;
+            if let Some(compatibility) = cipher.compatibility {
+                db_conn.pragma_update(None, "cipher_compatibility", compatibility)?;
+            }
+            // SQLCipher only decrypts the database when it is first read.
+            db_conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))?;
+        }
+
+        Ok(Self::from_connection(db_conn))
+    }
+
+    /// Opens a read-only in-memory database holding a copy of the given database file.
+    ///
+    /// The data is checked by reading the schema,
+    /// which fails with [`ErrorCode::NotADatabase`](rusqlite::ffi::ErrorCode::NotADatabase)
+    /// if it is not a database.
+    pub(crate) fn deserialize(bytes: &[u8]) -> rusqlite::Result<Self> {
+        let mut db_conn = Connection::open_in_memory_with_flags(
+            OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        db_conn.deserialize_read_exact(MAIN_DB, bytes, bytes.len(), true)?;
+        // SQLite only reads the data when it is first queried.
+        db_conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))?;
+
+        Ok(Self::from_connection(db_conn))
+    }
+
+    /// Checks that the database has the tables and columns read from libcangjie's database.
+    ///
+    /// # Errors
+    ///
+    /// - [`NewError::IncompatibleSchema`] if any of them is missing.
+    /// - [`NewError::DBError`] if reading the schema fails.
+    pub(crate) fn check_schema(&self) -> NewResult<()> {
+        // Only the tables with all their columns identify libcangjie's schema,
+        // as other databases may well have a table named `chars` or `codes`.
+        let mut complete_tables = 0;
+        let mut missing = Vec::new();
+        for (table, columns) in EXPECTED_COLUMNS {
+            let present = self.columns(table)?;
+            if present.is_empty() {
+                missing.push(table.to_owned());
+                continue;
+            }
+
+            let missing_columns = missing.len();
+            missing.extend(
+                columns
+                    .iter()
+                    .filter(|column| !present.iter().any(|present| present == *column))
+                    .map(|column| format!("{table}.{column}")),
+            );
+            if missing.len() == missing_columns {
+                complete_tables += 1;
+            }
+        }
+
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        let generation = if complete_tables == 0 {
+            SchemaGeneration::Unknown
+        } else {
+            SchemaGeneration::LibCangjie1
+        };
+        let versions = if self
+            .columns("codes")?
+            .iter()
+            .any(|column| column == "version")
+        {
+            self.versions()?
+        } else {
+            Vec::new()
+        };
+
+        Err(NewError::IncompatibleSchema {
+            generation,
+            missing,
+            versions,
+        })
+    }
+
+    /// Lists the columns of a table, which is empty if the table does not exist.
+    fn columns(&self, table: &str) -> rusqlite::Result<Vec<String>> {
+        let mut stmt = self
+            .db_conn
+            .prepare("SELECT name FROM pragma_table_info(?1)")?;
+        stmt.query_map((table,), |row| row.get(0))?.collect()
+    }
+
+    /// Lists the distinct integers in `codes.version`, in ascending order.
+    fn versions(&self) -> rusqlite::Result<Vec<i64>> {
+        let mut stmt = self.db_conn.prepare(
+            r"
+                SELECT DISTINCT version
+                FROM codes
+                WHERE typeof(version) = 'integer'
+                ORDER BY version
+            ",
+        )?;
+        stmt.query_map((), |row| row.get(0))?.collect()
+    }
+
+    /// Reads the whole `chars` table, in candidate order.
+    pub(crate) fn all_characters(&self) -> HowToTypeResult<Vec<(i64, CharacterInfo)>> {
+        let mut stmt = self.db_conn.prepare(concat!(
+            "SELECT ",
+            character_info_columns!(),
+            r"
+                FROM chars
+                ORDER BY frequency DESC, char_index
+            ",
+        ))?;
+        let mut rows = stmt.query(())?;
+
+        let mut result = Vec::new();
+        while let Some(row) = rows.next()? {
+            let char_index = row.get(0)?;
+            let info = read_character_info(row, char_index)?;
+
+            result.push((char_index, info));
+        }
+
+        Ok(result)
+    }
+
+    /// Reads all codes of a version, ordered by `char_index` and code.
+    pub(crate) fn all_codes(
+        &self,
+        version: CangjieVersion,
+    ) -> HowToTypeResult<Vec<(i64, CangjieCode)>> {
+        let mut stmt = self.db_conn.prepare(
+            r"
+                SELECT char_index, code
+                FROM codes
+                WHERE version = ?1
+                ORDER BY char_index, code
+            ",
+        )?;
+        let mut rows = stmt.query((version.to_db_version(),))?;
+
+        let mut result = Vec::new();
+        while let Some(row) = rows.next()? {
+            let char_index = row.get(0)?;
+            let code = read_code(row, 1, char_index)?;
+
+            result.push((char_index, code));
+        }
+
+        Ok(result)
+    }
+}
+
+impl CangjieDatabase for SqliteDatabase {
+    fn codes_for_character(
+        &self,
+        character: &str,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<SmallVec<[CangjieCode; 1]>> {
+        let mut stmt = self.db_conn.prepare_cached(concat!(
+            r"
+                SELECT chars.char_index, codes.code
+                FROM chars
+                JOIN codes
+                  ON chars.char_index = codes.char_index
+                WHERE chars.chchar = ?1 AND codes.version = ?2 AND
+            ",
+            filter_condition!(),
+            r"
+                ORDER BY codes.code
+            ",
+        ))?;
+        let mut rows = stmt.query((character, version.to_db_version(), filter.bits()))?;
+
+        let mut result = SmallVec::new();
+        while let Some(row) = rows.next()? {
+            let char_index = row.get(0)?;
+            let code = read_code(row, 1, char_index)?;
+
+            result.push(code);
+        }
+
+        Ok(result)
+    }
+
+    fn characters_for_code(
+        &self,
+        code: &CangjieCode,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<String>> {
+        let mut stmt = self.db_conn.prepare_cached(concat!(
+            r"
+                SELECT chars.char_index, chars.chchar
+                FROM chars
+                JOIN codes
+                  ON chars.char_index = codes.char_index
+                WHERE codes.code = ?1 AND codes.version = ?2 AND
+            ",
+            filter_condition!(),
+            r"
+                ORDER BY chars.frequency DESC, chars.char_index
+            ",
+        ))?;
+        let mut rows = stmt.query((
+            code.codes().to_string(),
+            version.to_db_version(),
+            filter.bits(),
+        ))?;
+
+        let mut result = Vec::new();
+        while let Some(row) = rows.next()? {
+            let char_index = row.get(0)?;
+            let character = read_character(row, 1, char_index)?;
+
+            result.push(character);
+        }
+
+        Ok(result)
+    }
+
+    fn entries(
+        &self,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<(String, CangjieCode)>> {
+        let mut stmt = self.db_conn.prepare_cached(concat!(
+            r"
+                SELECT chars.char_index, chars.chchar, codes.code
+                FROM chars
+                JOIN codes
+                  ON chars.char_index = codes.char_index
+                WHERE codes.code GLOB ?1 AND codes.version = ?2 AND
+            ",
+            filter_condition!(),
+            r"
+                ORDER BY chars.frequency DESC, chars.char_index, codes.code
+            ",
+        ))?;
+        let mut rows = stmt.query(("*", version.to_db_version(), filter.bits()))?;
+
+        let mut result = Vec::new();
+        while let Some(row) = rows.next()? {
+            let char_index = row.get(0)?;
+            let character = read_character(row, 1, char_index)?;
+            let code = read_code(row, 2, char_index)?;
+
+            result.push((character, code));
+        }
+
+        Ok(result)
+    }
+
+    fn entries_with_flags(
+        &self,
+        version: CangjieVersion,
+    ) -> HowToTypeResult<Vec<(String, CangjieCode, CangjieFilter)>> {
+        let mut stmt = self.db_conn.prepare_cached(concat!(
+            "SELECT ",
+            character_info_columns!(),
+            r"
+                , codes.code
+                FROM chars
+                JOIN codes USING (char_index)
+                WHERE codes.version = ?1
+                ORDER BY chars.frequency DESC, chars.char_index, codes.code
+            ",
+        ))?;
+        let mut rows = stmt.query((version.to_db_version(),))?;
+
+        let mut result = Vec::new();
+        while let Some(row) = rows.next()? {
+            let char_index = row.get(0)?;
+            let info = read_character_info(row, char_index)?;
+            let code = read_code(row, 13, char_index)?;
+            let flags = CangjieFilter::from_info(&info);
+
+            result.push((info.character, code, flags));
+        }
+
+        Ok(result)
+    }
+
+    fn character_info(&self, character: &str) -> HowToTypeResult<Option<CharacterInfo>> {
+        let mut stmt = self.db_conn.prepare_cached(concat!(
+            "SELECT ",
+            character_info_columns!(),
+            r"
+                FROM chars
+                WHERE chchar = ?1
+            ",
+        ))?;
+        let mut rows = stmt.query((character,))?;
+
+        let Some(row) = rows.next()? else {
+            return Ok(None);
+        };
+        let char_index = row.get(0)?;
+
+        read_character_info(row, char_index).map(Some)
+    }
+
+    fn search(
+        &self,
+        pattern: &CodePattern,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<(String, CangjieCode)>> {
+        let mut stmt = self.db_conn.prepare_cached(concat!(
+            r"
+                SELECT chars.char_index, chars.chchar, codes.code
+                FROM chars
+                JOIN codes
+                  ON chars.char_index = codes.char_index
+                WHERE codes.code GLOB ?1 AND codes.version = ?2 AND
+            ",
+            filter_condition!(),
+            r"
+                ORDER BY codes.code, chars.frequency DESC, chars.char_index
+            ",
+        ))?;
+        let mut rows = stmt.query((
+            pattern.codes().to_string(),
+            version.to_db_version(),
+            filter.bits(),
+        ))?;
+
+        let mut result = Vec::new();
+        while let Some(row) = rows.next()? {
+            let char_index = row.get(0)?;
+            let character = read_character(row, 1, char_index)?;
+            let code = read_code(row, 2, char_index)?;
+
+            result.push((character, code));
+        }
+
+        Ok(result)
+    }
+
+    fn characters_matching(
+        &self,
+        pattern: &CodePattern,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<String>> {
+        let mut stmt = self.db_conn.prepare_cached(concat!(
+            r"
+                SELECT chars.char_index, chars.chchar
+                FROM chars
+                JOIN codes
+                  ON chars.char_index = codes.char_index
+                WHERE codes.code GLOB ?1 AND codes.version = ?2 AND
+            ",
+            filter_condition!(),
+            r"
+                GROUP BY chars.char_index
+                ORDER BY chars.frequency DESC, chars.char_index
+            ",
+        ))?;
+        let mut rows = stmt.query((
+            pattern.codes().to_string(),
+            version.to_db_version(),
+            filter.bits(),
+        ))?;
+
+        let mut result = Vec::new();
+        while let Some(row) = rows.next()? {
+            let char_index = row.get(0)?;
+            let character = read_character(row, 1, char_index)?;
+
+            result.push(character);
+        }
+
+        Ok(result)
+    }
+}
+
+/// Reads the information about a character from a query result
+/// selecting [`character_info_columns!`].
+fn read_character_info(row: &Row<'_>, char_index: i64) -> HowToTypeResult<CharacterInfo> {
+    let simplified = match row.get_ref(2)? {
+        ValueRef::Null => None,
+        _ => Some(read_text(row, 2, char_index, "chars.simpchar")?)
+            .filter(|simplified| !simplified.is_empty()),
+    };
+
+    Ok(CharacterInfo {
+        character: read_character(row, 1, char_index)?,
+        simplified,
+        chinese: read_flag(row, 3, char_index, "chars.zh")?,
+        big5: read_flag(row, 4, char_index, "chars.big5")?,
+        hkscs: read_flag(row, 5, char_index, "chars.hkscs")?,
+        zhuyin: read_flag(row, 6, char_index, "chars.zhuyin")?,
+        kanji: read_flag(row, 7, char_index, "chars.kanji")?,
+        hiragana: read_flag(row, 8, char_index, "chars.hiragana")?,
+        katakana: read_flag(row, 9, char_index, "chars.katakana")?,
+        punctuation: read_flag(row, 10, char_index, "chars.punct")?,
+        symbol: read_flag(row, 11, char_index, "chars.symbol")?,
+        frequency: read_frequency(row, 12, char_index)?,
+    })
+}
+
+/// Reads a character from a query result, checking its type.
+fn read_character(row: &Row<'_>, index: usize, char_index: i64) -> HowToTypeResult<String> {
+    read_text(row, index, char_index, "chars.chchar")
+}
+
+/// Reads a text value from a query result, checking its type.
+fn read_text(
+    row: &Row<'_>,
+    index: usize,
+    char_index: i64,
+    column: &'static str,
+) -> HowToTypeResult<String> {
+    match row.get_ref(index)? {
+        ValueRef::Text(text) => str::from_utf8(text)
+            .map(String::from)
+            .map_err(|_| HowToTypeError::UnexpectedValueType { char_index, column }),
+        _ => Err(HowToTypeError::UnexpectedValueType { char_index, column }),
+    }
+}
+
+/// Reads a flag from a query result, checking its type.
+///
+/// `NULL` is treated as unset.
+fn read_flag(
+    row: &Row<'_>,
+    index: usize,
+    char_index: i64,
+    column: &'static str,
+) -> HowToTypeResult<bool> {
+    match row.get_ref(index)? {
+        ValueRef::Null => Ok(false),
+        ValueRef::Integer(flag) => Ok(flag != 0),
+        _ => Err(HowToTypeError::UnexpectedValueType { char_index, column }),
+    }
+}
+
+/// Reads the frequency from a query result, checking its type.
+///
+/// `NULL` is treated as zero.
+fn read_frequency(row: &Row<'_>, index: usize, char_index: i64) -> HowToTypeResult<u32> {
+    let error = HowToTypeError::UnexpectedValueType {
+        char_index,
+        column: "chars.frequency",
+    };
+
+    match row.get_ref(index)? {
+        ValueRef::Null => Ok(0),
+        ValueRef::Integer(frequency) => u32::try_from(frequency).map_err(|_| error),
+        _ => Err(error),
+    }
+}
+
+/// Reads a code from a query result, checking its type and content.
+fn read_code(row: &Row<'_>, index: usize, char_index: i64) -> HowToTypeResult<CangjieCode> {
+    let ValueRef::Text(raw) = row.get_ref(index)? else {
+        return Err(HowToTypeError::UnexpectedValueType {
+            char_index,
+            column: "codes.code",
+        });
+    };
+
+    raw.iter()
+        .map(|&code| CangjieRadical::try_from_code(code))
+        .collect::<Option<CangjieCode>>()
+        .filter(|code| !code.is_empty())
+        .ok_or_else(|| HowToTypeError::InvalidCode {
+            char_index,
+            raw: String::from_utf8_lossy(raw).into_owned(),
+        })
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use std::error::Error;
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use libcangjie_howtotype::{
+    AsyncCangjie, CangjieCode, CangjieVersion, CodePattern, HowToTypeError, LibCangjieHowToType,
+    SqlitePool,
+};
+
+fn assert_send<T: Send>(_: &T) {}
+
+#[tokio::test]
+async fn test_async_matches_blocking() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("async-matches-blocking", common::SAMPLE_DATA)?;
+    let cangjie = LibCangjieHowToType::builder().path(&path).open()?;
+    let async_cangjie = AsyncCangjie::from_database(SqlitePool::open(&path)?);
+
+    for character in ["日", "明", "喵", "屬", "貓"] {
+        assert_eq!(
+            async_cangjie
+                .how_to_type(character, CangjieVersion::V3)
+                .await?,
+            cangjie.how_to_type(character, CangjieVersion::V3)?,
+        );
+        assert_eq!(
+            async_cangjie.how_to_type_quick(character).await?,
+            cangjie.how_to_type_quick(character)?,
+        );
+        assert_eq!(
+            async_cangjie.character_info(character).await?,
+            cangjie.character_info(character)?,
+        );
+    }
+
+    let code = CangjieCode::from_codes(b"a");
+    assert_eq!(
+        async_cangjie
+            .characters_for(&code, CangjieVersion::V3)
+            .await?,
+        ["日", "曰"],
+    );
+
+    let pattern = CodePattern::from_codes(b"*");
+    assert_eq!(
+        async_cangjie.search(&pattern, CangjieVersion::V3).await?,
+        cangjie.search(&pattern, CangjieVersion::V3)?,
+    );
+
+    let text = async_cangjie
+        .how_to_type_text("明日喵", CangjieVersion::V3)
+        .await?;
+    let expected = cangjie
+        .how_to_type_text("明日喵", CangjieVersion::V3)
+        .map(|result| result.map(|(segment, codes)| (segment.to_owned(), codes)))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(text, expected);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_async_concurrent() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("async-concurrent", common::SAMPLE_DATA)?;
+    let cangjie = AsyncCangjie::from_database(SqlitePool::open(&path)?);
+
+    let tasks = (0..32)
+        .map(|_| {
+            let cangjie = cangjie.clone();
+            tokio::spawn(async move { cangjie.how_to_type("喵", CangjieVersion::V3).await })
+        })
+        .collect::<Vec<_>>();
+    for task in tasks {
+        assert_eq!(*task.await??, [CangjieCode::from_codes(b"rtw")]);
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_async_max_concurrent_queries() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("async-max-concurrent-queries", common::SAMPLE_DATA)?;
+    let cangjie = AsyncCangjie::from_database(SqlitePool::open(&path)?);
+    assert_eq!(
+        cangjie.max_concurrent_queries(),
+        AsyncCangjie::<SqlitePool>::DEFAULT_MAX_CONCURRENT_QUERIES,
+    );
+
+    let cangjie = cangjie.with_max_concurrent_queries(NonZeroUsize::MIN);
+    assert_eq!(cangjie.clone().max_concurrent_queries(), 1);
+
+    let tasks = (0..32)
+        .map(|_| {
+            let cangjie = cangjie.clone();
+            tokio::spawn(async move { cangjie.how_to_type("喵", CangjieVersion::V3).await })
+        })
+        .collect::<Vec<_>>();
+    for task in tasks {
+        assert_eq!(*task.await??, [CangjieCode::from_codes(b"rtw")]);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_async_runtime_shutdown() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("async-runtime-shutdown", common::SAMPLE_DATA)?;
+    let cangjie = AsyncCangjie::from_database(SqlitePool::open(&path)?);
+
+    let shut_down = tokio::runtime::Builder::new_current_thread().build()?;
+    let handle = shut_down.handle().clone();
+    shut_down.shutdown_background();
+
+    let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+    let result = runtime.block_on(async {
+        // Queries are spawned on the runtime entered when they are first polled.
+        let _guard = handle.enter();
+        cangjie.how_to_type("喵", CangjieVersion::V3).await
+    });
+    assert!(matches!(result, Err(HowToTypeError::RuntimeShutdown)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_cancellation() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("async-cancellation", common::SAMPLE_DATA)?;
+    let cangjie = AsyncCangjie::from_database(SqlitePool::open(&path)?);
+
+    let pattern = CodePattern::from_codes(b"*");
+    for _ in 0..16 {
+        let future = cangjie.search(&pattern, CangjieVersion::V3);
+        assert_send(&future);
+        // Dropped, whether it has completed or not.
+        let _ = tokio::time::timeout(Duration::ZERO, future).await;
+    }
+
+    assert_eq!(
+        *cangjie.how_to_type("日", CangjieVersion::V3).await?,
+        [CangjieCode::from_codes(b"a")],
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_in_memory() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("async-in-memory", common::SAMPLE_DATA)?;
+    let in_memory = LibCangjieHowToType::builder()
+        .path(&path)
+        .open()?
+        .to_in_memory()?;
+    let cangjie = AsyncCangjie::from(in_memory);
+
+    assert_eq!(
+        *cangjie.how_to_type("明", CangjieVersion::V3).await?,
+        [CangjieCode::from_codes(b"ab")],
+    );
+    assert_eq!(
+        *cangjie.blocking().how_to_type("明", CangjieVersion::V3)?,
+        [CangjieCode::from_codes(b"ab")],
+    );
+
+    Ok(())
+}
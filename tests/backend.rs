@@ -0,0 +1,225 @@
+mod common;
+
+use std::error::Error;
+
+use libcangjie_howtotype::smallvec::SmallVec;
+use libcangjie_howtotype::{
+    CangjieCode, CangjieDatabase, CangjieFilter, CangjieVersion, CharacterInfo, CodePattern,
+    HowToTypeError, HowToTypeResult, LibCangjieHowToType, SqliteDatabase,
+};
+
+/// A backend implementing only the required methods,
+/// relying on the default implementations for the rest.
+struct RequiredOnly(SqliteDatabase);
+
+impl CangjieDatabase for RequiredOnly {
+    fn codes_for_character(
+        &self,
+        character: &str,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<SmallVec<[CangjieCode; 1]>> {
+        self.0.codes_for_character(character, version, filter)
+    }
+
+    fn characters_for_code(
+        &self,
+        code: &CangjieCode,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<String>> {
+        self.0.characters_for_code(code, version, filter)
+    }
+
+    fn entries(
+        &self,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<(String, CangjieCode)>> {
+        self.0.entries(version, filter)
+    }
+
+    fn character_info(&self, character: &str) -> HowToTypeResult<Option<CharacterInfo>> {
+        self.0.character_info(character)
+    }
+}
+
+#[test]
+fn test_entries() -> Result<(), Box<dyn Error>> {
+    let database = common::open_db("backend-entries", common::SAMPLE_DATA)?;
+
+    let entries = database.entries(CangjieVersion::V3, CangjieFilter::empty())?;
+    let expected = [
+        ("日", "a"),
+        ("明", "ab"),
+        ("昌", "aa"),
+        ("昌", "aaa"),
+        ("曰", "a"),
+        ("晶", "aaa"),
+        ("喵", "rtw"),
+        ("屬", "syyi"),
+    ]
+    .map(|(character, code)| {
+        (
+            String::from(character),
+            CangjieCode::from_codes(code.as_bytes()),
+        )
+    });
+    assert_eq!(entries, expected);
+
+    let entries = database.entries(CangjieVersion::V3, CangjieFilter::HKSCS)?;
+    assert_eq!(entries.len(), 4);
+
+    let entries = database.entries_with_flags(CangjieVersion::V3)?;
+    assert_eq!(entries.len(), 8);
+    assert_eq!(
+        entries[0],
+        (
+            String::from("日"),
+            CangjieCode::from_codes(b"a"),
+            CangjieFilter::CHINESE | CangjieFilter::BIG5,
+        ),
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_default_search() -> Result<(), Box<dyn Error>> {
+    let database = common::open_db("backend-default-search", common::SAMPLE_DATA)?;
+    let required_only = RequiredOnly(common::open_db(
+        "backend-default-search-required-only",
+        common::SAMPLE_DATA,
+    )?);
+
+    for pattern in ["*", "a*", "a?", "?", "*a", "a*a", "b"] {
+        let pattern = pattern.parse::<CodePattern>()?;
+        for filter in [CangjieFilter::empty(), CangjieFilter::BIG5] {
+            assert_eq!(
+                required_only.search(&pattern, CangjieVersion::V3, filter)?,
+                database.search(&pattern, CangjieVersion::V3, filter)?,
+                "search for {} with {filter:?}",
+                pattern.codes(),
+            );
+            assert_eq!(
+                required_only.characters_matching(&pattern, CangjieVersion::V3, filter)?,
+                database.characters_matching(&pattern, CangjieVersion::V3, filter)?,
+                "characters matching {} with {filter:?}",
+                pattern.codes(),
+            );
+        }
+    }
+
+    for version in [CangjieVersion::V3, CangjieVersion::V5] {
+        assert_eq!(
+            required_only.entries_with_flags(version)?,
+            database.entries_with_flags(version)?,
+            "entries with flags in {version:?}",
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_from_database() -> Result<(), Box<dyn Error>> {
+    let cangjie = LibCangjieHowToType::from_database(RequiredOnly(common::open_db(
+        "backend-from-database",
+        common::SAMPLE_DATA,
+    )?));
+
+    let how_to_type = cangjie.how_to_type("昌", CangjieVersion::V3)?;
+    assert_eq!(
+        *how_to_type,
+        [
+            CangjieCode::from_codes(b"aa"),
+            CangjieCode::from_codes(b"aaa")
+        ],
+    );
+
+    let how_to_type = cangjie.how_to_type_quick("晶")?;
+    assert_eq!(how_to_type[0].code, CangjieCode::from_codes(b"aa"));
+    assert_eq!(how_to_type[0].position, 2);
+
+    let results = cangjie.search(&CodePattern::from_codes(b"a?"), CangjieVersion::V3)?;
+    assert_eq!(
+        results,
+        [
+            (String::from("昌"), CangjieCode::from_codes(b"aa")),
+            (String::from("明"), CangjieCode::from_codes(b"ab")),
+        ],
+    );
+
+    Ok(())
+}
+
+/// A backend that knows how to type characters,
+/// but never lists them as the candidates of any code.
+struct NoCandidates(SqliteDatabase);
+
+impl CangjieDatabase for NoCandidates {
+    fn codes_for_character(
+        &self,
+        character: &str,
+        version: CangjieVersion,
+        filter: CangjieFilter,
+    ) -> HowToTypeResult<SmallVec<[CangjieCode; 1]>> {
+        self.0.codes_for_character(character, version, filter)
+    }
+
+    fn characters_for_code(
+        &self,
+        _code: &CangjieCode,
+        _version: CangjieVersion,
+        _filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    fn entries(
+        &self,
+        _version: CangjieVersion,
+        _filter: CangjieFilter,
+    ) -> HowToTypeResult<Vec<(String, CangjieCode)>> {
+        Ok(Vec::new())
+    }
+
+    fn character_info(&self, character: &str) -> HowToTypeResult<Option<CharacterInfo>> {
+        self.0.character_info(character)
+    }
+}
+
+#[test]
+fn test_inconsistent_quick() -> Result<(), Box<dyn Error>> {
+    let cangjie = LibCangjieHowToType::from_database(NoCandidates(common::open_db(
+        "backend-inconsistent-quick",
+        common::SAMPLE_DATA,
+    )?));
+
+    let result = cangjie.how_to_type_quick("晶");
+    assert!(matches!(
+        result,
+        Err(HowToTypeError::NotACandidate { ref character, ref code })
+            if character == "晶" && *code == CangjieCode::from_codes(b"aa"),
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_inconsistent_positions() -> Result<(), Box<dyn Error>> {
+    let cangjie = LibCangjieHowToType::from_database(NoCandidates(common::open_db(
+        "backend-inconsistent-positions",
+        common::SAMPLE_DATA,
+    )?));
+
+    let result =
+        cangjie.how_to_type_with_positions("晶", CangjieVersion::V3, CangjieFilter::empty());
+    assert!(matches!(
+        result,
+        Err(HowToTypeError::NotACandidate { ref character, ref code })
+            if character == "晶" && *code == CangjieCode::from_codes(b"aaa"),
+    ));
+
+    Ok(())
+}
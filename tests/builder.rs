@@ -0,0 +1,38 @@
+use std::error::Error;
+
+use libcangjie_howtotype::rusqlite::{Connection, OpenFlags};
+use libcangjie_howtotype::{CangjieCode, CangjieVersion, LibCangjieHowToType};
+
+#[test]
+fn test_builder_path() -> Result<(), Box<dyn Error>> {
+    let cangjie = LibCangjieHowToType::builder()
+        .path("/usr/share/libcangjie/cangjie.db")
+        .open()?;
+
+    let how_to_type = cangjie.how_to_type("喵", CangjieVersion::V3)?;
+    assert_eq!(*how_to_type, [CangjieCode::from_radicals("口廿田")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_builder_nonexistent_path() {
+    let result = LibCangjieHowToType::builder()
+        .path("/nonexistent/cangjie.db")
+        .open();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_from_connection() -> Result<(), Box<dyn Error>> {
+    let db_conn = Connection::open_with_flags(
+        "/usr/share/libcangjie/cangjie.db",
+        OpenFlags::SQLITE_OPEN_READ_ONLY,
+    )?;
+    let cangjie = LibCangjieHowToType::from_connection(db_conn);
+
+    let how_to_type = cangjie.how_to_type("喵", CangjieVersion::V3)?;
+    assert_eq!(*how_to_type, [CangjieCode::from_radicals("口廿田")]);
+
+    Ok(())
+}
@@ -0,0 +1,150 @@
+mod common;
+
+use std::error::Error;
+use std::num::NonZeroUsize;
+use std::thread;
+
+use libcangjie_howtotype::rusqlite::Connection;
+use libcangjie_howtotype::{
+    CacheStats, CangjieCode, CangjieFilter, CangjieVersion, LibCangjieHowToType,
+};
+
+fn capacity(capacity: usize) -> NonZeroUsize {
+    NonZeroUsize::new(capacity).unwrap()
+}
+
+fn hits_and_misses(stats: CacheStats) -> (u64, u64) {
+    (stats.hits, stats.misses)
+}
+
+#[test]
+fn test_cache_hits_and_misses() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("cache-hits-and-misses", common::SAMPLE_DATA)?;
+    let cangjie = LibCangjieHowToType::builder()
+        .path(&path)
+        .cache_capacity(capacity(10))
+        .open()?;
+
+    for _ in 0..3 {
+        let how_to_type = cangjie.how_to_type("喵", CangjieVersion::V3)?;
+        assert_eq!(*how_to_type, [CangjieCode::from_codes(b"rtw")]);
+    }
+    let stats = cangjie.stats();
+    assert_eq!(hits_and_misses(stats), (2, 1));
+    assert_eq!((stats.len, stats.capacity), (1, 10));
+
+    // Unknown characters are cached too.
+    cangjie.how_to_type("貓", CangjieVersion::V3)?;
+    assert!(cangjie.how_to_type("貓", CangjieVersion::V3)?.is_empty());
+    assert_eq!(hits_and_misses(cangjie.stats()), (3, 2));
+
+    cangjie.clear_cache();
+    let stats = cangjie.stats();
+    assert_eq!(hits_and_misses(stats), (0, 0));
+    assert_eq!((stats.len, stats.capacity), (0, 10));
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_keys() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("cache-keys", common::SAMPLE_DATA)?;
+    let cangjie = LibCangjieHowToType::builder()
+        .path(&path)
+        .open()?
+        .with_cache(capacity(10));
+
+    cangjie.how_to_type("喵", CangjieVersion::V3)?;
+    cangjie.how_to_type("喵", CangjieVersion::V5)?;
+    assert_eq!(hits_and_misses(cangjie.stats()), (0, 2));
+
+    let how_to_type =
+        cangjie.how_to_type_with_filter("喵", CangjieVersion::V3, CangjieFilter::BIG5)?;
+    assert!(how_to_type.is_empty());
+    assert_eq!(hits_and_misses(cangjie.stats()), (0, 3));
+
+    let how_to_type = cangjie.how_to_type("喵", CangjieVersion::V3)?;
+    assert_eq!(*how_to_type, [CangjieCode::from_codes(b"rtw")]);
+    assert_eq!(hits_and_misses(cangjie.stats()), (1, 3));
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_eviction() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("cache-eviction", common::SAMPLE_DATA)?;
+    let cangjie = LibCangjieHowToType::builder()
+        .path(&path)
+        .cache_capacity(capacity(1))
+        .open()?;
+
+    cangjie.how_to_type("日", CangjieVersion::V3)?;
+    cangjie.how_to_type("喵", CangjieVersion::V3)?;
+    cangjie.how_to_type("日", CangjieVersion::V3)?;
+    let stats = cangjie.stats();
+    assert_eq!(hits_and_misses(stats), (0, 3));
+    assert_eq!(stats.len, 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_skips_database() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("cache-skips-database", common::SAMPLE_DATA)?;
+    let cangjie = LibCangjieHowToType::builder()
+        .path(&path)
+        .cache_capacity(capacity(10))
+        .open()?;
+    let text = cangjie
+        .how_to_type_text("日喵", CangjieVersion::V3)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Connection::open(&path)?.execute_batch("DELETE FROM codes;")?;
+
+    assert_eq!(
+        cangjie
+            .how_to_type_text("日喵", CangjieVersion::V3)
+            .collect::<Result<Vec<_>, _>>()?,
+        text,
+    );
+    assert_eq!(hits_and_misses(cangjie.stats()), (2, 2));
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_shared() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("cache-shared", common::SAMPLE_DATA)?;
+    let shared = LibCangjieHowToType::builder()
+        .path(&path)
+        .cache_capacity(capacity(10))
+        .open_shared()?;
+
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..50 {
+                    let how_to_type = shared.how_to_type("喵", CangjieVersion::V3).unwrap();
+                    assert_eq!(*how_to_type, [CangjieCode::from_codes(b"rtw")]);
+                }
+            });
+        }
+    });
+    let (hits, misses) = hits_and_misses(shared.stats());
+    assert_eq!(hits + misses, 200);
+    assert!(misses >= 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_no_cache() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("no-cache", common::SAMPLE_DATA)?;
+    let cangjie = LibCangjieHowToType::builder().path(&path).open()?;
+
+    cangjie.how_to_type("喵", CangjieVersion::V3)?;
+    cangjie.how_to_type("喵", CangjieVersion::V3)?;
+    assert_eq!(cangjie.stats(), CacheStats::default());
+
+    Ok(())
+}
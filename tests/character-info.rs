@@ -0,0 +1,79 @@
+mod common;
+
+use std::error::Error;
+
+use libcangjie_howtotype::{HowToTypeError, LibCangjieHowToType};
+
+#[test]
+fn test_character_info() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db(
+        "character-info",
+        r"
+            INSERT INTO chars VALUES(1, '屬', '属', 1, 1, 0, 0, 1, 0, 0, 0, 0, 800);
+            INSERT INTO chars VALUES(2, 'の', NULL, 0, 0, 0, 0, 0, 1, 0, 0, 0, NULL);
+        ",
+    )?;
+    let cangjie = LibCangjieHowToType::builder().path(&path).open()?;
+
+    let info = cangjie
+        .character_info("屬")?
+        .expect("屬 is in the database");
+    assert_eq!(info.character, "屬");
+    assert_eq!(info.simplified.as_deref(), Some("属"));
+    assert!(info.chinese);
+    assert!(info.big5);
+    assert!(!info.hkscs);
+    assert!(!info.zhuyin);
+    assert!(info.kanji);
+    assert!(!info.hiragana);
+    assert!(!info.katakana);
+    assert!(!info.punctuation);
+    assert!(!info.symbol);
+    assert_eq!(info.frequency, 800);
+
+    let info = cangjie
+        .character_info("の")?
+        .expect("の is in the database");
+    assert_eq!(info.simplified, None);
+    assert!(!info.chinese);
+    assert!(info.hiragana);
+    assert_eq!(info.frequency, 0);
+
+    assert_eq!(cangjie.character_info("😀")?, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_character_info_unexpected_value_type() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db(
+        "character-info-unexpected-value-type",
+        r"
+            INSERT INTO chars VALUES(1, '屬', '属', 1, 'yes', 0, 0, 1, 0, 0, 0, 0, 800);
+        ",
+    )?;
+    let cangjie = LibCangjieHowToType::builder().path(&path).open()?;
+
+    assert!(matches!(
+        cangjie.character_info("屬"),
+        Err(HowToTypeError::UnexpectedValueType {
+            char_index: 1,
+            column: "chars.big5",
+        }),
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_character_info_system() -> Result<(), Box<dyn Error>> {
+    let cangjie = LibCangjieHowToType::new()?;
+
+    let info = cangjie
+        .character_info("喵")?
+        .expect("喵 is in the database");
+    assert_eq!(info.character, "喵");
+    assert!(info.chinese);
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use std::error::Error;
+use std::process::Command;
+
+use assert_cmd::prelude::*;
+use libcangjie_howtotype::rusqlite::Connection;
+use predicates::prelude::*;
+
+#[test]
+fn test_cli_db() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("--db")
+        .arg("/usr/share/libcangjie/cangjie.db")
+        .arg("喵");
+    cmd.assert().success().stdout(predicate::eq("口廿田\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_db_nonexistent() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("--db").arg("/nonexistent/cangjie.db").arg("喵");
+    cmd.assert()
+        .code(exitcode::OSFILE)
+        .stdout(predicate::eq(""))
+        .stderr(predicate::str::starts_with(
+            "Error: Cannot open libcangjie's database",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_db_incompatible() -> Result<(), Box<dyn Error>> {
+    let path = common::temp_db_path("cli-db-incompatible")?;
+    Connection::open(&path)?.execute_batch("CREATE TABLE words(word TEXT);")?;
+
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("--db").arg(&path).arg("喵");
+    cmd.assert()
+        .code(exitcode::DATAERR)
+        .stdout(predicate::eq(""))
+        .stderr(predicate::eq(
+            "Error: Cannot open libcangjie's database: Incompatible database schema \
+             (unknown schema, missing: chars, codes, versions: none)\n",
+        ));
+
+    Ok(())
+}
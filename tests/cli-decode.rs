@@ -0,0 +1,54 @@
+use std::error::Error;
+use std::process::Command;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+#[test]
+fn test_cli_decode_codes() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("decode").arg("rtw");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("喵"));
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_decode_radicals() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("-C").arg("5").arg("decode").arg("尸水田戈");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("屬"));
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_decode_nothing() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("decode").arg("zzzzz");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::eq(""))
+        .stderr(predicate::eq(
+            "Error: No character can be typed with 'ＺＺＺＺＺ'\n",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_decode_invalid() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("decode").arg("喵");
+    cmd.assert().code(exitcode::USAGE).stdout(predicate::eq(""));
+
+    Ok(())
+}
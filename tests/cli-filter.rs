@@ -0,0 +1,45 @@
+use std::error::Error;
+use std::process::Command;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+#[test]
+fn test_cli_filter() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("--filter").arg("chinese").arg("喵");
+    cmd.assert().success().stdout(predicate::eq("口廿田\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_filter_excluded() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("--filter").arg("hiragana").arg("喵");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::eq(""))
+        .stderr(predicate::eq("Error: Don't know how to type '喵'\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_filter_repeated() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("--filter")
+        .arg("hiragana")
+        .arg("--filter")
+        .arg("chinese")
+        .arg("decode")
+        .arg("rtw");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("喵"));
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use std::error::Error;
+use std::process::Command;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+#[test]
+fn test_cli_info() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db(
+        "cli-info",
+        r"
+            INSERT INTO chars VALUES(1, '屬', '属', 1, 1, 0, 0, 1, 0, 0, 0, 0, 800);
+        ",
+    )?;
+
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("--db").arg(&path).arg("info").arg("屬");
+    cmd.assert().success().stdout(predicate::eq(
+        "Character: 屬\n\
+         Simplified: 属\n\
+         Chinese: yes\n\
+         Big5: yes\n\
+         HKSCS: no\n\
+         Zhuyin: no\n\
+         Kanji: yes\n\
+         Hiragana: no\n\
+         Katakana: no\n\
+         Punctuation: no\n\
+         Symbol: no\n\
+         Frequency: 800\n",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_info_not_found() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("info").arg("😀");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::eq(""))
+        .stderr(predicate::eq(
+            "Error: '😀' is not in libcangjie's database\n",
+        ));
+
+    Ok(())
+}
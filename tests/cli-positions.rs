@@ -0,0 +1,49 @@
+use std::error::Error;
+use std::process::Command;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+#[test]
+fn test_cli_positions() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("--positions").arg("喵");
+    cmd.assert().success().stdout(predicate::str::is_match(
+        r"^口廿田 \(\d+(st|nd|rd|th) candidate\)\n$",
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_positions_code() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("-p").arg("-f").arg("code").arg("喵");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("rtw ("));
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_positions_with_quick() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("--positions").arg("--quick").arg("喵");
+    cmd.assert().code(exitcode::USAGE).stdout(predicate::eq(""));
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_positions_with_subcommand() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("--positions").arg("decode").arg("rtw");
+    cmd.assert().code(exitcode::USAGE).stdout(predicate::eq(""));
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use std::error::Error;
+use std::process::Command;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+#[test]
+fn test_cli_quick() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("--quick").arg("喵");
+    cmd.assert().success().stdout(predicate::str::is_match(
+        r"^口田 \(\d+(st|nd|rd|th) candidate\)\n$",
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_quick_code() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("--quick").arg("-f").arg("code").arg("喵");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("rw ("));
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_quick_with_version() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("--quick").arg("-C").arg("5").arg("喵");
+    cmd.assert().code(exitcode::USAGE).stdout(predicate::eq(""));
+
+    Ok(())
+}
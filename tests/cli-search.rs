@@ -0,0 +1,44 @@
+use std::error::Error;
+use std::process::Command;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+#[test]
+fn test_cli_search_radicals() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("search").arg("口*田");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("喵 口廿田\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_search_codes() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("-f").arg("code").arg("search").arg("r?w");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("喵 rtw\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_search_nothing() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("search").arg("zzzzz*");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::eq(""))
+        .stderr(predicate::eq(
+            "Error: No character has a code matching 'ＺＺＺＺＺ*'\n",
+        ));
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+mod common;
+
+use std::error::Error;
+use std::process::Command;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+#[test]
+fn test_cli_invalid_code() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db(
+        "cli-invalid-code",
+        r"
+            INSERT INTO chars VALUES(1, '喵', '', 1, 1, 0, 0, 0, 0, 0, 0, 0, 500);
+            INSERT INTO codes VALUES(1, 3, 'R-W', 0);
+        ",
+    )?;
+
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("--db").arg(&path).arg("喵");
+    cmd.assert()
+        .code(exitcode::DATAERR)
+        .stdout(predicate::eq(""))
+        .stderr(predicate::eq(
+            "Error: Unexpected content in libcangjie's database: \
+             Invalid code \"R-W\" for character 1\n",
+        ));
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use std::error::Error;
+use std::process::Command;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+
+#[test]
+fn test_cli_zhong() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("--zhong").arg("decode").arg("zzzzz");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::eq(""))
+        .stderr(predicate::eq(
+            "Error: No character can be typed with '重重重重重'\n",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_decode_zhong() -> Result<(), Box<dyn Error>> {
+    let mut cmd = Command::cargo_bin("libcangjie-howtotype")?;
+
+    cmd.arg("decode").arg("重重重重重");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::eq(""))
+        .stderr(predicate::eq(
+            "Error: No character can be typed with 'ＺＺＺＺＺ'\n",
+        ));
+
+    Ok(())
+}
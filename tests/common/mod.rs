@@ -0,0 +1,133 @@
+use std::env;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+use libcangjie_howtotype::SqliteDatabase;
+use libcangjie_howtotype::rusqlite::Connection;
+
+/// A file path in the temporary directory, removing the file when dropped.
+#[derive(Debug)]
+pub struct TempPath(PathBuf);
+
+impl Deref for TempPath {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempPath {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<OsStr> for TempPath {
+    fn as_ref(&self) -> &OsStr {
+        self.0.as_os_str()
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// Sample rows for [`create_db`], shared by the tests that need no particular data.
+///
+/// - 日 and 曰 share the code `a`, and 明, 晶 and 昌 start with it.
+/// - 昌 has two codes, `aa` and `aaa`, the latter shared with 晶.
+/// - 屬 has a simplified form, no frequency, and different codes in each version.
+/// - 〇 is a symbol with a code only in version 5.
+/// - 喵 has the same code in both versions.
+#[allow(dead_code, reason = "not every test uses the sample data")]
+pub const SAMPLE_DATA: &str = r"
+    INSERT INTO chars VALUES(1, '日', '', 1, 1, 0, 0, 0, 0, 0, 0, 0, 9000);
+    INSERT INTO chars VALUES(2, '曰', '', 1, 0, 1, 0, 0, 0, 0, 0, 0, 700);
+    INSERT INTO chars VALUES(3, '明', '', 1, 1, 0, 0, 0, 0, 0, 0, 0, 8000);
+    INSERT INTO chars VALUES(4, '晶', '', 1, 1, 0, 0, 0, 0, 0, 0, 0, 600);
+    INSERT INTO chars VALUES(5, '昌', '', 1, 0, 1, 0, 0, 0, 0, 0, 0, 800);
+    INSERT INTO chars VALUES(6, '屬', '属', 1, 1, 0, 0, 0, 0, 0, 0, 0, NULL);
+    INSERT INTO chars VALUES(7, '〇', NULL, 0, 0, 0, 0, 0, 0, 0, 0, 1, 100);
+    INSERT INTO chars VALUES(8, '喵', '', 1, 0, 1, 0, 0, 0, 0, 0, 0, 100);
+    INSERT INTO codes VALUES(1, 3, 'a', 0);
+    INSERT INTO codes VALUES(1, 5, 'a', 0);
+    INSERT INTO codes VALUES(2, 3, 'a', 0);
+    INSERT INTO codes VALUES(3, 3, 'ab', 0);
+    INSERT INTO codes VALUES(4, 3, 'aaa', 0);
+    INSERT INTO codes VALUES(5, 3, 'aa', 0);
+    INSERT INTO codes VALUES(5, 3, 'aaa', 0);
+    INSERT INTO codes VALUES(6, 3, 'syyi', 0);
+    INSERT INTO codes VALUES(6, 5, 'sewi', 0);
+    INSERT INTO codes VALUES(7, 5, 'a', 0);
+    INSERT INTO codes VALUES(8, 3, 'rtw', 0);
+    INSERT INTO codes VALUES(8, 5, 'rtw', 0);
+";
+
+/// Returns a path for a file with the given name in the temporary directory,
+/// removing any leftover file there.
+pub fn temp_path(name: &str) -> Result<TempPath, Box<dyn Error>> {
+    let path = env::temp_dir().join(format!(
+        "libcangjie-howtotype-{}-{name}",
+        std::process::id(),
+    ));
+    if path.exists() {
+        fs::remove_file(&path)?;
+    }
+
+    Ok(TempPath(path))
+}
+
+/// Returns a path for a database with the given name in the temporary directory.
+pub fn temp_db_path(name: &str) -> Result<TempPath, Box<dyn Error>> {
+    temp_path(&format!("{name}.db"))
+}
+
+/// Creates a database with libcangjie's schema in the temporary directory,
+/// populated by the given SQL.
+pub fn create_db(name: &str, sql: &str) -> Result<TempPath, Box<dyn Error>> {
+    let path = temp_db_path(name)?;
+
+    let db_conn = Connection::open(&path)?;
+    db_conn.execute_batch(
+        r"
+            CREATE TABLE chars(
+                char_index INTEGER PRIMARY KEY ASC,
+                chchar TEXT UNIQUE,
+                simpchar TEXT,
+                zh INTEGER,
+                big5 INTEGER,
+                hkscs INTEGER,
+                zhuyin INTEGER,
+                kanji INTEGER,
+                hiragana INTEGER,
+                katakana INTEGER,
+                punct INTEGER,
+                symbol INTEGER,
+                frequency INTEGER
+            );
+            CREATE TABLE codes(
+                char_index INTEGER,
+                version INTEGER,
+                code TEXT,
+                short INTEGER,
+                UNIQUE(char_index, version, code, short)
+            );
+        ",
+    )?;
+    db_conn.execute_batch(sql)?;
+
+    Ok(path)
+}
+
+/// Creates a database as in [`create_db`] and opens it as a backend.
+#[allow(dead_code, reason = "not every test uses a backend directly")]
+pub fn open_db(name: &str, sql: &str) -> Result<SqliteDatabase, Box<dyn Error>> {
+    let path = create_db(name, sql)?;
+    Ok(SqliteDatabase::from_connection(Connection::open(path)?))
+}
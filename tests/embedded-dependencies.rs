@@ -0,0 +1,29 @@
+use std::error::Error;
+use std::process::Command;
+
+/// The `embedded-data` feature alone must not depend on SQLite at runtime.
+#[test]
+fn test_embedded_no_runtime_sqlite() -> Result<(), Box<dyn Error>> {
+    let output = Command::new(env!("CARGO"))
+        .args(["tree", "--offline", "--no-default-features"])
+        .args(["--features", "embedded-data", "--edges", "normal"])
+        .args(["--prefix", "none", "--format", "{p}"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+
+    let tree = String::from_utf8(output.stdout)?;
+    for package in ["rusqlite", "libsqlite3-sys"] {
+        let prefix = format!("{package} ");
+        assert!(
+            !tree.lines().any(|line| line.starts_with(&prefix)),
+            "{package} is a runtime dependency:\n{tree}",
+        );
+    }
+
+    Ok(())
+}
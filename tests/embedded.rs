@@ -0,0 +1,91 @@
+use std::error::Error;
+
+use libcangjie_howtotype::{CangjieCode, CangjieVersion, EmbeddedCangjie, LibCangjieHowToType};
+
+#[test]
+fn test_embedded() -> Result<(), Box<dyn Error>> {
+    let cangjie = LibCangjieHowToType::<EmbeddedCangjie>::embedded();
+
+    let how_to_type = cangjie.how_to_type("喵", CangjieVersion::V3)?;
+    assert_eq!(*how_to_type, [CangjieCode::from_radicals("口廿田")]);
+
+    let info = cangjie.character_info("喵")?.expect("喵 is embedded");
+    assert!(info.chinese);
+
+    assert!(cangjie.how_to_type("😀", CangjieVersion::V3)?.is_empty());
+    assert_eq!(cangjie.character_info("😀")?, None);
+
+    Ok(())
+}
+
+/// The embedded data is generated from the same database used at runtime,
+/// unless `LIBCANGJIE_DB` points elsewhere.
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_embedded_matches_sqlite() -> Result<(), Box<dyn Error>> {
+    use libcangjie_howtotype::{CangjieDatabase, CangjieFilter, CodePattern};
+
+    let sqlite = LibCangjieHowToType::new()?;
+    let sqlite = sqlite.database();
+    let embedded = EmbeddedCangjie;
+
+    let filters = [
+        CangjieFilter::empty(),
+        CangjieFilter::BIG5,
+        CangjieFilter::HKSCS | CangjieFilter::SYMBOLS,
+    ];
+    for version in [CangjieVersion::V3, CangjieVersion::V5] {
+        let entries = sqlite.entries(version, CangjieFilter::empty())?;
+        assert_eq!(
+            embedded.entries_with_flags(version)?,
+            sqlite.entries_with_flags(version)?
+        );
+
+        for filter in filters {
+            assert_eq!(
+                embedded.entries(version, filter)?,
+                sqlite.entries(version, filter)?
+            );
+
+            for (character, code) in &entries {
+                assert_eq!(
+                    embedded.codes_for_character(character, version, filter)?,
+                    sqlite.codes_for_character(character, version, filter)?,
+                    "{character} in {version:?} with {filter:?}",
+                );
+                assert_eq!(
+                    embedded.characters_for_code(code, version, filter)?,
+                    sqlite.characters_for_code(code, version, filter)?,
+                    "{} in {version:?} with {filter:?}",
+                    code.codes(),
+                );
+            }
+
+            for pattern in ["*", "a*", "a?", "?", "*a", "s*i", "z"] {
+                let pattern = pattern.parse::<CodePattern>()?;
+                assert_eq!(
+                    embedded.search(&pattern, version, filter)?,
+                    sqlite.search(&pattern, version, filter)?,
+                    "search for {} in {version:?} with {filter:?}",
+                    pattern.codes(),
+                );
+                assert_eq!(
+                    embedded.characters_matching(&pattern, version, filter)?,
+                    sqlite.characters_matching(&pattern, version, filter)?,
+                    "characters matching {} in {version:?} with {filter:?}",
+                    pattern.codes(),
+                );
+            }
+        }
+
+        for (character, _) in entries {
+            assert_eq!(
+                embedded.character_info(&character)?,
+                sqlite.character_info(&character)?,
+                "{character}",
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use std::error::Error;
+
+use libcangjie_howtotype::{
+    CangjieCode, CangjieFilter, CangjieVersion, CodePattern, LibCangjieHowToType,
+};
+
+const DATA: &str = r"
+    INSERT INTO chars VALUES(1, '日', '', 1, 1, 0, 0, 1, 0, 0, 0, 0, 9000);
+    INSERT INTO chars VALUES(2, '曰', '', 1, 0, 1, 0, 0, 0, 0, 0, 0, 700);
+    INSERT INTO chars VALUES(3, 'ヨ', '', 0, 0, 0, 0, 0, 0, 1, 0, 0, 100);
+    INSERT INTO chars VALUES(4, '〇', '', 0, 0, 0, 0, 0, 0, 0, 0, 0, 50);
+    INSERT INTO codes VALUES(1, 3, 'a', 0);
+    INSERT INTO codes VALUES(2, 3, 'a', 0);
+    INSERT INTO codes VALUES(3, 3, 'a', 0);
+    INSERT INTO codes VALUES(4, 3, 'a', 0);
+";
+
+#[test]
+fn test_how_to_type_with_filter() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("filter-how-to-type", DATA)?;
+    let cangjie = LibCangjieHowToType::builder().path(&path).open()?;
+
+    let code = CangjieCode::from_codes(b"a");
+    for (character, filter, expected) in [
+        ("日", CangjieFilter::BIG5, true),
+        ("日", CangjieFilter::HKSCS, false),
+        ("日", CangjieFilter::HKSCS | CangjieFilter::KANJI, true),
+        ("ヨ", CangjieFilter::KATAKANA, true),
+        ("ヨ", CangjieFilter::all() - CangjieFilter::KATAKANA, false),
+        ("〇", CangjieFilter::all(), false),
+        ("〇", CangjieFilter::empty(), true),
+    ] {
+        let how_to_type = cangjie.how_to_type_with_filter(character, CangjieVersion::V3, filter)?;
+        if expected {
+            assert_eq!(
+                &*how_to_type,
+                std::slice::from_ref(&code),
+                "{character} with {filter:?}"
+            );
+        } else {
+            assert!(how_to_type.is_empty(), "{character} with {filter:?}");
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_characters_for_with_filter() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("filter-characters-for", DATA)?;
+    let cangjie = LibCangjieHowToType::builder().path(&path).open()?;
+
+    let code = CangjieCode::from_codes(b"a");
+    assert_eq!(
+        cangjie.characters_for(&code, CangjieVersion::V3)?,
+        ["日", "曰", "ヨ", "〇"],
+    );
+    assert_eq!(
+        cangjie.characters_for_with_filter(&code, CangjieVersion::V3, CangjieFilter::CHINESE)?,
+        ["日", "曰"],
+    );
+    assert_eq!(
+        cangjie.characters_for_with_filter(
+            &code,
+            CangjieVersion::V3,
+            CangjieFilter::HKSCS | CangjieFilter::KATAKANA,
+        )?,
+        ["曰", "ヨ"],
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_search_with_filter() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("filter-search", DATA)?;
+    let cangjie = LibCangjieHowToType::builder().path(&path).open()?;
+
+    let results = cangjie.search_with_filter(
+        &CodePattern::from_codes(b"*"),
+        CangjieVersion::V3,
+        CangjieFilter::BIG5,
+    )?;
+    assert_eq!(
+        results,
+        [(String::from("日"), CangjieCode::from_codes(b"a"))]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_filter_matches() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("filter-matches", DATA)?;
+    let cangjie = LibCangjieHowToType::builder().path(&path).open()?;
+
+    let info = cangjie
+        .character_info("曰")?
+        .expect("曰 is in the database");
+    assert!(CangjieFilter::HKSCS.matches(&info));
+    assert!((CangjieFilter::BIG5 | CangjieFilter::CHINESE).matches(&info));
+    assert!(!CangjieFilter::BIG5.matches(&info));
+
+    Ok(())
+}
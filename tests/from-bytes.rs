@@ -0,0 +1,49 @@
+mod common;
+
+use std::error::Error;
+use std::fs;
+
+use libcangjie_howtotype::rusqlite::{Connection, MAIN_DB};
+use libcangjie_howtotype::{CangjieCode, CangjieVersion, LibCangjieHowToType, NewError};
+
+#[test]
+fn test_from_bytes() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("from-bytes", common::SAMPLE_DATA)?;
+    let bytes = fs::read(&path)?;
+    drop(path);
+
+    for cangjie in [
+        LibCangjieHowToType::from_bytes(&bytes[..])?,
+        LibCangjieHowToType::from_bytes(bytes)?,
+    ] {
+        let how_to_type = cangjie.how_to_type("喵", CangjieVersion::V3)?;
+        assert_eq!(*how_to_type, [CangjieCode::from_codes(b"rtw")]);
+        assert_eq!(
+            cangjie.characters_for(&CangjieCode::from_codes(b"a"), CangjieVersion::V3)?,
+            ["日", "曰"],
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_from_bytes_serialized() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("from-bytes-serialized", common::SAMPLE_DATA)?;
+    let bytes = Connection::open(&path)?.serialize(MAIN_DB)?.to_vec();
+    drop(path);
+
+    let cangjie = LibCangjieHowToType::from_bytes(bytes)?;
+    let how_to_type = cangjie.how_to_type("日", CangjieVersion::V3)?;
+    assert_eq!(*how_to_type, [CangjieCode::from_codes(b"a")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_from_bytes_invalid() {
+    for bytes in [&b""[..], b"not a database", &[0; 4096]] {
+        let result = LibCangjieHowToType::from_bytes(bytes);
+        assert!(matches!(result, Err(NewError::DBError(_))), "{bytes:?}");
+    }
+}
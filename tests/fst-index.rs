@@ -0,0 +1,136 @@
+mod common;
+
+use std::error::Error;
+use std::fs::{self, File};
+
+use libcangjie_howtotype::{
+    CangjieCode, CangjieDatabase, CangjieFilter, CangjieVersion, CodePattern, FstIndex, IndexError,
+    SqliteDatabase,
+};
+use memmap2::Mmap;
+
+const FILTERS: [CangjieFilter; 3] = [
+    CangjieFilter::empty(),
+    CangjieFilter::BIG5,
+    CangjieFilter::HKSCS.union(CangjieFilter::SYMBOLS),
+];
+
+fn assert_matches_database<D: AsRef<[u8]>>(
+    index: &FstIndex<D>,
+    database: &SqliteDatabase,
+) -> Result<(), Box<dyn Error>> {
+    for version in [CangjieVersion::V3, CangjieVersion::V5] {
+        for filter in FILTERS {
+            for code in ["a", "aa", "aaa", "ab", "syyi", "sewi", "z"] {
+                let code = CangjieCode::from_codes(code.as_bytes());
+                assert_eq!(
+                    index.characters_for_code(&code, version, filter)?,
+                    database.characters_for_code(&code, version, filter)?,
+                    "{code:?} in {version:?} with {filter:?}",
+                );
+            }
+
+            for pattern in [
+                "*", "a*", "*a", "?", "a?", "a*a", "*a*", "s??i", "s*", "", "z*",
+            ] {
+                let pattern = CodePattern::from_codes(pattern.as_bytes());
+                assert_eq!(
+                    index.search(&pattern, version, filter)?,
+                    database.search(&pattern, version, filter)?,
+                    "{pattern:?} in {version:?} with {filter:?}",
+                );
+            }
+
+            for prefix in ["", "a", "aa", "s", "sy", "z"] {
+                let pattern = CodePattern::from_codes(format!("{prefix}*").as_bytes());
+                let prefix = CangjieCode::from_codes(prefix.as_bytes());
+                assert_eq!(
+                    index.starting_with(&prefix, version, filter)?,
+                    database.search(&pattern, version, filter)?,
+                    "{prefix:?} in {version:?} with {filter:?}",
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_fst_index_matches_sqlite() -> Result<(), Box<dyn Error>> {
+    let database = common::open_db("fst-index-matches-sqlite", common::SAMPLE_DATA)?;
+    let index = FstIndex::build(&database)?;
+
+    assert_eq!(index.len(), 12);
+    assert_matches_database(&index, &database)
+}
+
+#[test]
+fn test_fst_index_candidate_order() -> Result<(), Box<dyn Error>> {
+    let database = common::open_db("fst-index-candidate-order", common::SAMPLE_DATA)?;
+    let index = FstIndex::build(&database)?;
+
+    let results = index.search(
+        &CodePattern::from_codes(b"a*"),
+        CangjieVersion::V3,
+        CangjieFilter::empty(),
+    )?;
+    assert_eq!(
+        results,
+        [
+            ("日".to_string(), CangjieCode::from_codes(b"a")),
+            ("曰".to_string(), CangjieCode::from_codes(b"a")),
+            ("昌".to_string(), CangjieCode::from_codes(b"aa")),
+            ("昌".to_string(), CangjieCode::from_codes(b"aaa")),
+            ("晶".to_string(), CangjieCode::from_codes(b"aaa")),
+            ("明".to_string(), CangjieCode::from_codes(b"ab")),
+        ],
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_fst_index_mmap() -> Result<(), Box<dyn Error>> {
+    let database = common::open_db("fst-index-mmap", common::SAMPLE_DATA)?;
+    let path = common::temp_path("fst-index-mmap.fst")?;
+    fs::write(&path, FstIndex::build(&database)?.as_bytes())?;
+
+    let file = File::open(&path)?;
+    // SAFETY: The file is not modified while it is mapped.
+    let index = FstIndex::new(unsafe { Mmap::map(&file)? })?;
+    assert_matches_database(&index, &database)
+}
+
+#[test]
+fn test_fst_index_invalid() {
+    assert!(matches!(
+        FstIndex::new(b"not an index".to_vec()),
+        Err(IndexError::Fst(_)),
+    ));
+}
+
+#[test]
+fn test_fst_index_invalid_entry() -> Result<(), Box<dyn Error>> {
+    let map = libcangjie_howtotype::fst::Map::from_iter([("3a\0日", 0), ("3q!\0日", 0)])?;
+    let index = FstIndex::new(map.into_fst().into_inner())?;
+
+    let error = index
+        .starting_with(
+            &CangjieCode::default(),
+            CangjieVersion::V3,
+            CangjieFilter::empty(),
+        )
+        .unwrap_err();
+    assert!(matches!(error, IndexError::InvalidEntry { key } if key == "3q!\0日".as_bytes()));
+    assert_eq!(
+        index.characters_for_code(
+            &CangjieCode::from_codes(b"a"),
+            CangjieVersion::V3,
+            CangjieFilter::empty(),
+        )?,
+        ["日"],
+    );
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use std::error::Error;
+
+use libcangjie_howtotype::{
+    CangjieCode, CangjieDatabase, CangjieFilter, CangjieVersion, CodePattern, InMemoryCangjie,
+    LibCangjieHowToType,
+};
+
+/// The sample data with a code of a character missing from `chars`.
+fn data() -> String {
+    format!(
+        "{}INSERT INTO codes VALUES(99, 3, 'a', 0);",
+        common::SAMPLE_DATA,
+    )
+}
+
+#[test]
+fn test_in_memory_matches_sqlite() -> Result<(), Box<dyn Error>> {
+    let database = common::open_db("in-memory-matches-sqlite", &data())?;
+    let in_memory = InMemoryCangjie::from_sqlite(&database)?;
+
+    let filters = [
+        CangjieFilter::empty(),
+        CangjieFilter::BIG5,
+        CangjieFilter::HKSCS | CangjieFilter::SYMBOLS,
+    ];
+    for version in [CangjieVersion::V3, CangjieVersion::V5] {
+        for filter in filters {
+            for character in ["日", "曰", "明", "晶", "昌", "屬", "〇", "😀"] {
+                assert_eq!(
+                    in_memory.codes_for_character(character, version, filter)?,
+                    database.codes_for_character(character, version, filter)?,
+                    "{character} in {version:?} with {filter:?}",
+                );
+            }
+
+            for code in ["a", "aa", "aaa", "ab", "syyi", "sewi", "z"] {
+                let code = CangjieCode::from_codes(code.as_bytes());
+                assert_eq!(
+                    in_memory.characters_for_code(&code, version, filter)?,
+                    database.characters_for_code(&code, version, filter)?,
+                    "{} in {version:?} with {filter:?}",
+                    code.codes(),
+                );
+            }
+
+            for pattern in ["*", "a*", "a?", "?", "*a", "a*a", "s*i", "b"] {
+                let pattern = pattern.parse::<CodePattern>()?;
+                assert_eq!(
+                    in_memory.search(&pattern, version, filter)?,
+                    database.search(&pattern, version, filter)?,
+                    "search for {} in {version:?} with {filter:?}",
+                    pattern.codes(),
+                );
+                assert_eq!(
+                    in_memory.characters_matching(&pattern, version, filter)?,
+                    database.characters_matching(&pattern, version, filter)?,
+                    "characters matching {} in {version:?} with {filter:?}",
+                    pattern.codes(),
+                );
+            }
+
+            assert_eq!(
+                in_memory.entries(version, filter)?,
+                database.entries(version, filter)?,
+                "entries in {version:?} with {filter:?}",
+            );
+        }
+
+        assert_eq!(
+            in_memory.entries_with_flags(version)?,
+            database.entries_with_flags(version)?,
+            "entries with flags in {version:?}",
+        );
+    }
+
+    for character in ["日", "屬", "〇", "😀"] {
+        assert_eq!(
+            in_memory.character_info(character)?,
+            database.character_info(character)?,
+            "{character}",
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_to_in_memory() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("to-in-memory", &data())?;
+    let cangjie = LibCangjieHowToType::builder()
+        .path(&path)
+        .open()?
+        .to_in_memory()?;
+
+    let how_to_type = cangjie.how_to_type("屬", CangjieVersion::V5)?;
+    assert_eq!(*how_to_type, [CangjieCode::from_codes(b"sewi")]);
+
+    let how_to_type = cangjie.how_to_type_quick("晶")?;
+    assert_eq!(how_to_type[0].code, CangjieCode::from_codes(b"aa"));
+    assert_eq!(how_to_type[0].position, 2);
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use libcangjie_howtotype::{CangjieCode, CangjieRadical, PackedCangjieCode};
+
+fn codes() -> Vec<CangjieCode> {
+    [
+        "", "a", "aa", "aaaaa", "ab", "az", "b", "r", "rt", "rtw", "rtwz", "rw", "rz", "rzzzz",
+        "s", "sewi", "syyi", "z", "za", "zzzzz",
+    ]
+    .into_iter()
+    .map(|codes| CangjieCode::from_codes(codes.as_bytes()))
+    .collect()
+}
+
+fn pack(code: &CangjieCode) -> PackedCangjieCode {
+    PackedCangjieCode::try_from(code).unwrap()
+}
+
+#[test]
+fn test_round_trip() {
+    for code in codes() {
+        let packed = pack(&code);
+        assert_eq!(packed.len(), code.len());
+        assert_eq!(packed.is_empty(), code.is_empty());
+        assert_eq!(packed.iter().collect::<Vec<_>>(), *code);
+        assert_eq!(CangjieCode::from(packed), code);
+        assert_eq!(PackedCangjieCode::from_bits(packed.to_bits()), Some(packed));
+    }
+}
+
+#[test]
+fn test_too_long() {
+    let code = CangjieCode::from_codes(b"abcdef");
+    let error = PackedCangjieCode::try_from(&code).unwrap_err();
+    assert_eq!(error.code_len(), 6);
+    assert_eq!(PackedCangjieCode::new(&[CangjieRadical::A; 6]), None);
+}
+
+#[test]
+fn test_order() {
+    for a in codes() {
+        for b in codes() {
+            assert_eq!(pack(&a).cmp(&pack(&b)), a.cmp(&b), "{a:?} vs {b:?}");
+        }
+    }
+}
+
+#[test]
+fn test_prefix() {
+    let mut packed = codes().iter().map(pack).collect::<Vec<_>>();
+    packed.sort_unstable();
+
+    for prefix in codes() {
+        let range = pack(&prefix).prefix_range();
+        let start = packed.partition_point(|code| code < range.start());
+        let end = packed.partition_point(|code| code <= range.end());
+
+        let expected = codes()
+            .into_iter()
+            .filter(|code| code.starts_with(&prefix))
+            .collect::<Vec<_>>();
+        let found = packed[start..end]
+            .iter()
+            .map(|&code| CangjieCode::from(code))
+            .collect::<Vec<_>>();
+        assert_eq!(found, expected, "prefix {prefix:?}");
+
+        for code in codes() {
+            assert_eq!(
+                pack(&code).starts_with(pack(&prefix)),
+                code.starts_with(&prefix),
+            );
+        }
+    }
+}
+
+#[test]
+fn test_invalid_bits() {
+    // Length 1 without a radical.
+    assert_eq!(PackedCangjieCode::from_bits(1), None);
+    // Length 6.
+    assert_eq!(PackedCangjieCode::from_bits(6), None);
+    // A radical past the length.
+    assert_eq!(PackedCangjieCode::from_bits(1 << 23), None);
+    // Radical 27.
+    assert_eq!(PackedCangjieCode::from_bits(27 << 23 | 1), None);
+    // Bits above the radicals.
+    assert_eq!(PackedCangjieCode::from_bits(1 << 28), None);
+}
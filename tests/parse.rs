@@ -0,0 +1,97 @@
+use libcangjie_howtotype::{CangjieCode, CangjieRadical, CodePattern};
+
+#[test]
+fn test_try_from_u8() {
+    assert_eq!(CangjieRadical::try_from(b'z'), Ok(CangjieRadical::Z));
+    assert_eq!(CangjieRadical::try_from(b'Z'), Ok(CangjieRadical::Z));
+
+    let error = CangjieRadical::try_from(b'@').unwrap_err();
+    assert_eq!(error.character(), '@');
+    assert_eq!(error.position(), 0);
+}
+
+#[test]
+fn test_try_from_char() {
+    assert_eq!(CangjieRadical::try_from('w'), Ok(CangjieRadical::W));
+    assert_eq!(CangjieRadical::try_from('W'), Ok(CangjieRadical::W));
+    assert_eq!(CangjieRadical::try_from('ｗ'), Ok(CangjieRadical::W));
+    assert_eq!(CangjieRadical::try_from('Ｗ'), Ok(CangjieRadical::W));
+    assert_eq!(CangjieRadical::try_from('田'), Ok(CangjieRadical::W));
+    assert_eq!(CangjieRadical::try_from('Ｚ'), Ok(CangjieRadical::Z));
+
+    let error = CangjieRadical::try_from('喵').unwrap_err();
+    assert_eq!(error.character(), '喵');
+    assert_eq!(error.position(), 0);
+}
+
+#[test]
+fn test_radical_from_str() {
+    assert_eq!("w".parse(), Ok(CangjieRadical::W));
+    assert_eq!("Ｗ".parse(), Ok(CangjieRadical::W));
+    assert_eq!("田".parse(), Ok(CangjieRadical::W));
+    assert_eq!("重".parse(), Ok(CangjieRadical::Z));
+
+    let error = "喵".parse::<CangjieRadical>().unwrap_err();
+    assert_eq!(error.character(), '喵');
+    assert_eq!(error.position(), 0);
+
+    let error = "田口".parse::<CangjieRadical>().unwrap_err();
+    assert_eq!(error.character(), '口');
+    assert_eq!(error.position(), 1);
+
+    let error = "".parse::<CangjieRadical>().unwrap_err();
+    assert_eq!(error.position(), 0);
+    assert_eq!(error.to_string(), "Missing Cangjie radical at position 0");
+}
+
+#[test]
+fn test_try_from_codes() {
+    assert_eq!(
+        CangjieCode::try_from_codes(b"SyYi"),
+        Ok(CangjieCode::from_radicals("尸卜卜戈")),
+    );
+    assert_eq!(CangjieCode::try_from_codes(b""), Ok(CangjieCode::default()));
+
+    let error = CangjieCode::try_from_codes(b"sy yi").unwrap_err();
+    assert_eq!(error.character(), ' ');
+    assert_eq!(error.position(), 2);
+}
+
+#[test]
+fn test_try_from_radicals() {
+    assert_eq!(
+        CangjieCode::try_from_radicals("尸卜卜戈"),
+        Ok(CangjieCode::from_codes(b"syyi")),
+    );
+
+    // Codes are not radicals.
+    let error = CangjieCode::try_from_radicals("尸卜y戈").unwrap_err();
+    assert_eq!(error.character(), 'y');
+    assert_eq!(error.position(), 2);
+}
+
+#[test]
+fn test_from_str() {
+    assert_eq!("syyi".parse(), Ok(CangjieCode::from_codes(b"syyi")));
+    assert_eq!("ｓｙｙｉ".parse(), Ok(CangjieCode::from_codes(b"syyi")));
+    assert_eq!("尸卜yI".parse(), Ok(CangjieCode::from_codes(b"syyi")));
+
+    // The position is counted in characters, not bytes.
+    let error = "尸卜卜1".parse::<CangjieCode>().unwrap_err();
+    assert_eq!(error.character(), '1');
+    assert_eq!(error.position(), 3);
+    assert_eq!(
+        error.to_string(),
+        "Invalid Cangjie radical '1' at position 3"
+    );
+}
+
+#[test]
+fn test_pattern_from_str() {
+    assert_eq!("尸*戈".parse(), Ok(CodePattern::from_codes(b"s*i")));
+    assert_eq!("S?Yi".parse(), Ok(CodePattern::from_codes(b"s?yi")));
+
+    let error = "s*%".parse::<CodePattern>().unwrap_err();
+    assert_eq!(error.character(), '%');
+    assert_eq!(error.position(), 2);
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use std::error::Error;
+
+use libcangjie_howtotype::{
+    CandidateCode, CangjieCode, CangjieFilter, CangjieVersion, LibCangjieHowToType,
+};
+
+const DATA: &str = r"
+    INSERT INTO chars VALUES(1, '日', '', 1, 1, 0, 0, 0, 0, 0, 0, 0, 9000);
+    INSERT INTO chars VALUES(2, '曰', '', 1, 0, 1, 0, 0, 0, 0, 0, 0, 700);
+    INSERT INTO chars VALUES(3, '〇', '', 1, 0, 1, 0, 0, 0, 0, 0, 0, 700);
+    INSERT INTO codes VALUES(1, 3, 'a', 0);
+    INSERT INTO codes VALUES(2, 3, 'a', 0);
+    INSERT INTO codes VALUES(3, 3, 'a', 0);
+    INSERT INTO codes VALUES(3, 3, 'b', 0);
+";
+
+#[test]
+fn test_how_to_type_with_positions() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("positions", DATA)?;
+    let cangjie = LibCangjieHowToType::builder().path(&path).open()?;
+
+    let how_to_type =
+        cangjie.how_to_type_with_positions("日", CangjieVersion::V3, CangjieFilter::empty())?;
+    assert_eq!(
+        *how_to_type,
+        [CandidateCode {
+            code: CangjieCode::from_codes(b"a"),
+            position: 1,
+        }],
+    );
+
+    // Ties in frequency are broken by the order in the database.
+    let how_to_type =
+        cangjie.how_to_type_with_positions("〇", CangjieVersion::V3, CangjieFilter::empty())?;
+    assert_eq!(
+        *how_to_type,
+        [
+            CandidateCode {
+                code: CangjieCode::from_codes(b"a"),
+                position: 3,
+            },
+            CandidateCode {
+                code: CangjieCode::from_codes(b"b"),
+                position: 1,
+            },
+        ],
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_how_to_type_with_positions_filtered() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("positions-filtered", DATA)?;
+    let cangjie = LibCangjieHowToType::builder().path(&path).open()?;
+
+    let how_to_type =
+        cangjie.how_to_type_with_positions("曰", CangjieVersion::V3, CangjieFilter::empty())?;
+    assert_eq!(how_to_type[0].position, 2);
+
+    let how_to_type =
+        cangjie.how_to_type_with_positions("曰", CangjieVersion::V3, CangjieFilter::HKSCS)?;
+    assert_eq!(how_to_type[0].position, 1);
+
+    let how_to_type =
+        cangjie.how_to_type_with_positions("曰", CangjieVersion::V3, CangjieFilter::BIG5)?;
+    assert!(how_to_type.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use std::error::Error;
+
+use libcangjie_howtotype::{CangjieCode, LibCangjieHowToType};
+
+#[test]
+fn test_quick_code() {
+    assert_eq!(
+        CangjieCode::from_codes(b"syyi").quick(),
+        CangjieCode::from_codes(b"si"),
+    );
+    assert_eq!(
+        CangjieCode::from_codes(b"ab").quick(),
+        CangjieCode::from_codes(b"ab"),
+    );
+    assert_eq!(
+        CangjieCode::from_codes(b"a").quick(),
+        CangjieCode::from_codes(b"a"),
+    );
+    assert_eq!(CangjieCode::default().quick(), CangjieCode::default());
+}
+
+#[test]
+fn test_how_to_type_quick() -> Result<(), Box<dyn Error>> {
+    let cangjie = LibCangjieHowToType::new()?;
+
+    let how_to_type = cangjie.how_to_type_quick("喵")?;
+    assert_eq!(how_to_type.len(), 1);
+    assert_eq!(how_to_type[0].code, CangjieCode::from_radicals("口田"));
+    assert!(how_to_type[0].position >= 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_how_to_type_quick_dont_know() -> Result<(), Box<dyn Error>> {
+    let cangjie = LibCangjieHowToType::new()?;
+
+    let how_to_type = cangjie.how_to_type_quick("😀")?;
+    assert!(how_to_type.is_empty());
+
+    Ok(())
+}
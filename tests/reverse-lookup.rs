@@ -0,0 +1,40 @@
+use std::error::Error;
+
+use libcangjie_howtotype::{CangjieCode, CangjieVersion, LibCangjieHowToType};
+
+#[test]
+fn test_characters_for() -> Result<(), Box<dyn Error>> {
+    let cangjie = LibCangjieHowToType::new()?;
+
+    let characters =
+        cangjie.characters_for(&CangjieCode::from_codes(b"rtw"), CangjieVersion::V3)?;
+    assert!(characters.iter().any(|character| character == "喵"));
+
+    Ok(())
+}
+
+#[test]
+fn test_characters_for_versions() -> Result<(), Box<dyn Error>> {
+    let cangjie = LibCangjieHowToType::new()?;
+
+    let characters =
+        cangjie.characters_for(&CangjieCode::from_codes(b"syyi"), CangjieVersion::V3)?;
+    assert!(characters.iter().any(|character| character == "屬"));
+
+    let characters =
+        cangjie.characters_for(&CangjieCode::from_codes(b"sewi"), CangjieVersion::V5)?;
+    assert!(characters.iter().any(|character| character == "屬"));
+
+    Ok(())
+}
+
+#[test]
+fn test_characters_for_nothing() -> Result<(), Box<dyn Error>> {
+    let cangjie = LibCangjieHowToType::new()?;
+
+    let characters =
+        cangjie.characters_for(&CangjieCode::from_codes(b"zzzzz"), CangjieVersion::V3)?;
+    assert!(characters.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,173 @@
+mod common;
+
+use std::error::Error;
+use std::fs;
+
+use common::TempPath;
+use libcangjie_howtotype::rusqlite::Connection;
+use libcangjie_howtotype::{LibCangjieHowToType, NewError, SchemaGeneration, SqlitePool};
+
+/// Creates a database in the temporary directory from the given SQL,
+/// without libcangjie's schema.
+fn create_other_db(name: &str, sql: &str) -> Result<TempPath, Box<dyn Error>> {
+    let path = common::temp_db_path(name)?;
+
+    Connection::open(&path)?.execute_batch(sql)?;
+
+    Ok(path)
+}
+
+#[test]
+fn test_schema_compatible() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("schema-compatible", "")?;
+
+    LibCangjieHowToType::builder().path(&path).open()?;
+    LibCangjieHowToType::builder().path(&path).open_shared()?;
+    LibCangjieHowToType::from_bytes(fs::read(&path)?)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_schema_unknown() -> Result<(), Box<dyn Error>> {
+    let path = create_other_db(
+        "schema-unknown",
+        "CREATE TABLE words(word TEXT, reading TEXT);",
+    )?;
+
+    let error = LibCangjieHowToType::builder()
+        .path(&path)
+        .open()
+        .expect_err("The schema is incompatible");
+    match &error {
+        NewError::IncompatibleSchema {
+            generation,
+            missing,
+            versions,
+        } => {
+            assert_eq!(*generation, SchemaGeneration::Unknown);
+            assert_eq!(*missing, ["chars", "codes"]);
+            assert!(versions.is_empty());
+        }
+        error => panic!("Unexpected error: {error}"),
+    }
+    assert_eq!(
+        error.to_string(),
+        "Incompatible database schema (unknown schema, missing: chars, codes, versions: none)",
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_schema_missing_columns() -> Result<(), Box<dyn Error>> {
+    let path = create_other_db(
+        "schema-missing-columns",
+        r"
+            CREATE TABLE chars(char_index INTEGER PRIMARY KEY ASC, chchar TEXT UNIQUE);
+            CREATE TABLE codes(char_index INTEGER, version INTEGER, code TEXT);
+            INSERT INTO codes VALUES(1, 5, 'a');
+            INSERT INTO codes VALUES(1, 3, 'a');
+            INSERT INTO codes VALUES(2, 3, 'b');
+            INSERT INTO codes VALUES(3, 'x', 'c');
+        ",
+    )?;
+
+    let check = |result| match result {
+        Err(NewError::IncompatibleSchema {
+            generation,
+            missing,
+            versions,
+        }) => {
+            assert_eq!(generation, SchemaGeneration::LibCangjie1);
+            assert_eq!(
+                missing,
+                [
+                    "chars.simpchar",
+                    "chars.zh",
+                    "chars.big5",
+                    "chars.hkscs",
+                    "chars.zhuyin",
+                    "chars.kanji",
+                    "chars.hiragana",
+                    "chars.katakana",
+                    "chars.punct",
+                    "chars.symbol",
+                    "chars.frequency",
+                ],
+            );
+            assert_eq!(versions, [3, 5]);
+        }
+        result => panic!("Unexpected result: {result:?}"),
+    };
+    check(
+        LibCangjieHowToType::builder()
+            .path(&path)
+            .open()
+            .map(|_| ()),
+    );
+    check(SqlitePool::open(&path).map(|_| ()));
+    check(LibCangjieHowToType::from_bytes(fs::read(&path)?).map(|_| ()));
+
+    Ok(())
+}
+
+#[test]
+fn test_schema_missing_table() -> Result<(), Box<dyn Error>> {
+    let path = create_other_db(
+        "schema-missing-table",
+        r"
+            CREATE TABLE codes(char_index INTEGER, version INTEGER, code TEXT);
+            INSERT INTO codes VALUES(1, 3, 'a');
+        ",
+    )?;
+
+    let result = LibCangjieHowToType::builder().path(&path).open();
+    assert!(matches!(
+        result,
+        Err(NewError::IncompatibleSchema {
+            generation: SchemaGeneration::LibCangjie1,
+            ref missing,
+            ref versions,
+        }) if *missing == ["chars"] && *versions == [3],
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_schema_other_columns() -> Result<(), Box<dyn Error>> {
+    let path = create_other_db(
+        "schema-other-columns",
+        r"
+            CREATE TABLE chars(id INTEGER PRIMARY KEY, name TEXT);
+            CREATE TABLE codes(char_index INTEGER, code TEXT);
+        ",
+    )?;
+
+    let result = LibCangjieHowToType::builder().path(&path).open();
+    assert!(matches!(
+        result,
+        Err(NewError::IncompatibleSchema {
+            generation: SchemaGeneration::Unknown,
+            ref missing,
+            ref versions,
+        }) if missing.len() == 14
+            && missing[..2] == ["chars.char_index", "chars.chchar"]
+            && missing[13] == "codes.version"
+            && versions.is_empty(),
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_schema_not_a_database() -> Result<(), Box<dyn Error>> {
+    let path = common::temp_db_path("schema-not-a-database")?;
+    fs::write(&path, "not a database".repeat(100))?;
+
+    let result = LibCangjieHowToType::builder().path(&path).open();
+    assert!(matches!(result, Err(NewError::DBError(_))));
+
+    Ok(())
+}
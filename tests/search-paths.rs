@@ -0,0 +1,51 @@
+use std::env;
+use std::error::Error;
+use std::path::PathBuf;
+
+use libcangjie_howtotype::{CangjieCode, CangjieVersion, LibCangjieHowToType};
+
+// The environment is process-wide, so everything that modifies it
+// lives in this single test.
+#[test]
+fn test_search_paths() -> Result<(), Box<dyn Error>> {
+    // SAFETY: No other thread in this test binary reads the environment.
+    unsafe {
+        env::set_var("LIBCANGJIE_DB", "/opt/cangjie.db");
+        env::set_var("XDG_DATA_HOME", "/home/user/.local/share");
+        env::set_var("XDG_DATA_DIRS", "/nix/store/share:relative:/usr/share");
+    }
+
+    assert_eq!(
+        LibCangjieHowToType::search_paths(),
+        [
+            PathBuf::from("/opt/cangjie.db"),
+            PathBuf::from("/home/user/.local/share/libcangjie/cangjie.db"),
+            PathBuf::from("/nix/store/share/libcangjie/cangjie.db"),
+            PathBuf::from("/usr/share/libcangjie/cangjie.db"),
+        ],
+    );
+
+    // Nonexistent paths are skipped.
+    let cangjie = LibCangjieHowToType::new()?;
+    let how_to_type = cangjie.how_to_type("喵", CangjieVersion::V3)?;
+    assert_eq!(*how_to_type, [CangjieCode::from_radicals("口廿田")]);
+
+    // SAFETY: As above.
+    unsafe {
+        env::set_var("LIBCANGJIE_DB", "/usr/share/libcangjie/cangjie.db");
+        env::remove_var("XDG_DATA_HOME");
+        env::set_var("HOME", "/home/user");
+        env::remove_var("XDG_DATA_DIRS");
+    }
+
+    assert_eq!(
+        LibCangjieHowToType::search_paths(),
+        [
+            PathBuf::from("/usr/share/libcangjie/cangjie.db"),
+            PathBuf::from("/home/user/.local/share/libcangjie/cangjie.db"),
+            PathBuf::from("/usr/local/share/libcangjie/cangjie.db"),
+        ],
+    );
+
+    Ok(())
+}
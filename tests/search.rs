@@ -0,0 +1,62 @@
+use std::error::Error;
+
+use libcangjie_howtotype::{CangjieCode, CangjieVersion, CodePattern, LibCangjieHowToType};
+
+#[test]
+fn test_pattern_matches() {
+    let code = CangjieCode::from_codes(b"rtw");
+
+    assert!(CodePattern::from_codes(b"rtw").matches(&code));
+    assert!(CodePattern::from_codes(b"*").matches(&code));
+    assert!(CodePattern::from_codes(b"r*").matches(&code));
+    assert!(CodePattern::from_codes(b"*w").matches(&code));
+    assert!(CodePattern::from_codes(b"r*w").matches(&code));
+    assert!(CodePattern::from_codes(b"r?w").matches(&code));
+    assert!(CodePattern::from_codes(b"r*t*w").matches(&code));
+    assert!(CodePattern::from_codes(b"???").matches(&code));
+
+    assert!(!CodePattern::from_codes(b"rt").matches(&code));
+    assert!(!CodePattern::from_codes(b"r?").matches(&code));
+    assert!(!CodePattern::from_codes(b"????").matches(&code));
+    assert!(!CodePattern::from_codes(b"*r").matches(&code));
+}
+
+#[test]
+fn test_search() -> Result<(), Box<dyn Error>> {
+    let cangjie = LibCangjieHowToType::new()?;
+
+    let pattern = CodePattern::from_radicals("口*田");
+    let results = cangjie.search(&pattern, CangjieVersion::V3)?;
+    assert!(results.contains(&(String::from("喵"), CangjieCode::from_radicals("口廿田"))));
+    assert!(results.iter().all(|(_, code)| pattern.matches(code)));
+
+    Ok(())
+}
+
+#[test]
+fn test_search_single_wildcard() -> Result<(), Box<dyn Error>> {
+    let cangjie = LibCangjieHowToType::new()?;
+
+    let pattern = CodePattern::from_codes(b"s?wi");
+    let results = cangjie.search(&pattern, CangjieVersion::V5)?;
+    assert!(results.contains(&(String::from("屬"), CangjieCode::from_radicals("尸水田戈"))));
+    assert!(results.iter().all(|(_, code)| code.len() == 4));
+
+    Ok(())
+}
+
+#[test]
+fn test_search_exact() -> Result<(), Box<dyn Error>> {
+    let cangjie = LibCangjieHowToType::new()?;
+
+    let pattern = CodePattern::from_codes(b"rtw");
+    let results = cangjie.search(&pattern, CangjieVersion::V3)?;
+    assert!(results.contains(&(String::from("喵"), CangjieCode::from_radicals("口廿田"))));
+    assert!(
+        results
+            .iter()
+            .all(|(_, code)| **code == *CangjieCode::from_codes(b"rtw"))
+    );
+
+    Ok(())
+}
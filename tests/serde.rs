@@ -0,0 +1,129 @@
+use std::error::Error;
+
+use libcangjie_howtotype::{
+    CandidateCode, CangjieCode, CangjieFilter, CangjieRadical, CangjieVersion, CodePattern,
+    QuickCode,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn test_serde_radical() -> Result<(), Box<dyn Error>> {
+    assert_eq!(serde_json::to_string(&CangjieRadical::R)?, r#""r""#);
+    assert_eq!(
+        serde_json::from_str::<CangjieRadical>(r#""r""#)?,
+        CangjieRadical::R,
+    );
+    assert_eq!(
+        serde_json::from_str::<CangjieRadical>(r#""口""#)?,
+        CangjieRadical::R,
+    );
+    assert!(serde_json::from_str::<CangjieRadical>(r#""1""#).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_serde_code() -> Result<(), Box<dyn Error>> {
+    let code = CangjieCode::from_codes(b"rtw");
+
+    let json = serde_json::to_string(&code)?;
+    assert_eq!(json, r#""rtw""#);
+    assert_eq!(serde_json::from_str::<CangjieCode>(&json)?, code);
+    assert_eq!(serde_json::from_str::<CangjieCode>(r#""口廿田""#)?, code);
+    assert!(serde_json::from_str::<CangjieCode>(r#""r1w""#).is_err());
+    assert!(serde_json::from_str::<CangjieCode>("3").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_serde_code_representations() -> Result<(), Box<dyn Error>> {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Entry {
+        #[serde(with = "libcangjie_howtotype::serde::codes")]
+        codes: CangjieCode,
+        #[serde(with = "libcangjie_howtotype::serde::radicals")]
+        radicals: CangjieCode,
+    }
+
+    let entry = Entry {
+        codes: CangjieCode::from_codes(b"zxaj"),
+        radicals: CangjieCode::from_codes(b"zxaj"),
+    };
+
+    let json = serde_json::to_string(&entry)?;
+    assert_eq!(json, r#"{"codes":"zxaj","radicals":"Ｚ難日十"}"#);
+    assert_eq!(serde_json::from_str::<Entry>(&json)?, entry);
+
+    Ok(())
+}
+
+#[test]
+fn test_serde_pattern() -> Result<(), Box<dyn Error>> {
+    let pattern = CodePattern::from_codes(b"r*w?");
+
+    let json = serde_json::to_string(&pattern)?;
+    assert_eq!(json, r#""r*w?""#);
+    assert_eq!(serde_json::from_str::<CodePattern>(&json)?, pattern);
+    assert_eq!(serde_json::from_str::<CodePattern>(r#""口*田?""#)?, pattern);
+
+    Ok(())
+}
+
+#[test]
+fn test_serde_version() -> Result<(), Box<dyn Error>> {
+    for (version, json) in [(CangjieVersion::V3, "3"), (CangjieVersion::V5, "5")] {
+        assert_eq!(serde_json::to_string(&version)?, json);
+        assert_eq!(serde_json::from_str::<CangjieVersion>(json)?, version);
+    }
+    assert!(serde_json::from_str::<CangjieVersion>("4").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_serde_results() -> Result<(), Box<dyn Error>> {
+    let quick_code = QuickCode {
+        code: CangjieCode::from_codes(b"rw"),
+        position: 2,
+    };
+    let json = serde_json::to_string(&quick_code)?;
+    assert_eq!(json, r#"{"code":"rw","position":2}"#);
+    assert_eq!(serde_json::from_str::<QuickCode>(&json)?, quick_code);
+
+    let candidate_code = CandidateCode {
+        code: CangjieCode::from_codes(b"rtw"),
+        position: 1,
+    };
+    let json = serde_json::to_string(&candidate_code)?;
+    assert_eq!(
+        serde_json::from_str::<CandidateCode>(&json)?,
+        candidate_code
+    );
+
+    let filter = CangjieFilter::BIG5 | CangjieFilter::HKSCS;
+    let json = serde_json::to_string(&filter)?;
+    assert_eq!(serde_json::from_str::<CangjieFilter>(&json)?, filter);
+
+    Ok(())
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_serde_character_info() -> Result<(), Box<dyn Error>> {
+    use libcangjie_howtotype::{CharacterInfo, LibCangjieHowToType};
+
+    let cangjie = LibCangjieHowToType::new()?;
+    let info = cangjie
+        .character_info("屬")?
+        .expect("屬 is in the database");
+
+    let json = serde_json::to_string(&info)?;
+    assert_eq!(serde_json::from_str::<CharacterInfo>(&json)?, info);
+
+    let how_to_type = cangjie.how_to_type("屬", CangjieVersion::V5)?;
+    let json = serde_json::to_string(&how_to_type[..])?;
+    assert_eq!(json, r#"["sewi"]"#);
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use std::error::Error;
+use std::thread;
+
+use libcangjie_howtotype::{
+    CangjieCode, CangjieVersion, CodePattern, LibCangjieHowToType, NewError, SharedCangjie,
+    SqlitePool,
+};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_shared_is_send_sync() {
+    assert_send_sync::<SharedCangjie>();
+}
+
+#[test]
+fn test_shared_across_threads() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("shared-across-threads", common::SAMPLE_DATA)?;
+    let cangjie = LibCangjieHowToType::builder().path(&path).open()?;
+    let shared = LibCangjieHowToType::builder().path(&path).open_shared()?;
+
+    let expected = ["日", "曰", "明", "喵", "屬", "貓"]
+        .into_iter()
+        .map(|character| cangjie.how_to_type(character, CangjieVersion::V3))
+        .collect::<Result<Vec<_>, _>>()?;
+    thread::scope(|s| {
+        for _ in 0..8 {
+            s.spawn(|| {
+                for _ in 0..50 {
+                    let results = ["日", "曰", "明", "喵", "屬", "貓"]
+                        .into_iter()
+                        .map(|character| shared.how_to_type(character, CangjieVersion::V3))
+                        .collect::<Result<Vec<_>, _>>()
+                        .unwrap();
+                    assert_eq!(results, expected);
+                }
+            });
+        }
+    });
+
+    assert_eq!(
+        shared.characters_for(&CangjieCode::from_codes(b"a"), CangjieVersion::V3)?,
+        ["日", "曰"],
+    );
+    assert_eq!(
+        shared.search(&CodePattern::from_codes(b"a*"), CangjieVersion::V3)?,
+        cangjie.search(&CodePattern::from_codes(b"a*"), CangjieVersion::V3)?,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_shared_nonexistent_path() {
+    let result = SqlitePool::open("/nonexistent/cangjie.db");
+    assert!(matches!(result, Err(NewError::DBError(_))));
+}
+
+#[test]
+fn test_shared_path() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("shared-path", common::SAMPLE_DATA)?;
+    let pool = SqlitePool::open(&path)?;
+    assert_eq!(pool.path(), &*path);
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use std::error::Error;
+
+use common::TempPath;
+use libcangjie_howtotype::rusqlite::Connection;
+use libcangjie_howtotype::{CangjieCode, CangjieVersion, LibCangjieHowToType, NewError};
+
+const DATA: &str = r"
+    INSERT INTO chars VALUES(1, '喵', '', 1, 0, 1, 0, 0, 0, 0, 0, 0, 100);
+    INSERT INTO codes VALUES(1, 3, 'rtw', 0);
+";
+
+const KEY: &str = "correct horse battery staple";
+
+/// Creates a database encrypted with [`KEY`],
+/// with the settings of the given SQLCipher version if any.
+fn create_encrypted_db(name: &str, compatibility: Option<u8>) -> Result<TempPath, Box<dyn Error>> {
+    let plain = common::create_db(&format!("{name}-plain"), DATA)?;
+    let path = common::temp_db_path(name)?;
+
+    let db_conn = Connection::open(&plain)?;
+    db_conn.execute(
+        "ATTACH DATABASE ?1 AS encrypted KEY ?2",
+        (path.to_str().expect("Non-UTF-8 path"), KEY),
+    )?;
+    if let Some(compatibility) = compatibility {
+        db_conn.pragma_update(Some("encrypted"), "cipher_compatibility", compatibility)?;
+    }
+    db_conn.query_row("SELECT sqlcipher_export('encrypted')", [], |_| Ok(()))?;
+    db_conn.execute_batch("DETACH DATABASE encrypted;")?;
+
+    Ok(path)
+}
+
+#[test]
+fn test_sqlcipher_key() -> Result<(), Box<dyn Error>> {
+    let path = create_encrypted_db("sqlcipher-key", None)?;
+    let cangjie = LibCangjieHowToType::builder().path(&path).key(KEY).open()?;
+
+    let how_to_type = cangjie.how_to_type("喵", CangjieVersion::V3)?;
+    assert_eq!(*how_to_type, [CangjieCode::from_codes(b"rtw")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_sqlcipher_key_shared() -> Result<(), Box<dyn Error>> {
+    let path = create_encrypted_db("sqlcipher-key-shared", None)?;
+    let shared = LibCangjieHowToType::builder()
+        .path(&path)
+        .key(KEY)
+        .open_shared()?;
+
+    let how_to_type = shared.how_to_type("喵", CangjieVersion::V3)?;
+    assert_eq!(*how_to_type, [CangjieCode::from_codes(b"rtw")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_sqlcipher_wrong_key() -> Result<(), Box<dyn Error>> {
+    let path = create_encrypted_db("sqlcipher-wrong-key", None)?;
+
+    let result = LibCangjieHowToType::builder()
+        .path(&path)
+        .key("wrong")
+        .open();
+    assert!(matches!(result, Err(NewError::WrongKey)));
+
+    let result = LibCangjieHowToType::builder()
+        .path(&path)
+        .key("wrong")
+        .open_shared();
+    assert!(matches!(result, Err(NewError::WrongKey)));
+
+    Ok(())
+}
+
+#[test]
+fn test_sqlcipher_key_unencrypted() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db("sqlcipher-key-unencrypted", DATA)?;
+
+    let result = LibCangjieHowToType::builder().path(&path).key(KEY).open();
+    assert!(matches!(result, Err(NewError::WrongKey)));
+
+    Ok(())
+}
+
+#[test]
+fn test_sqlcipher_cipher_compatibility() -> Result<(), Box<dyn Error>> {
+    let path = create_encrypted_db("sqlcipher-cipher-compatibility", Some(3))?;
+
+    let result = LibCangjieHowToType::builder().path(&path).key(KEY).open();
+    assert!(matches!(result, Err(NewError::WrongKey)));
+
+    let cangjie = LibCangjieHowToType::builder()
+        .path(&path)
+        .key(KEY)
+        .cipher_compatibility(3)
+        .open()?;
+    let how_to_type = cangjie.how_to_type("喵", CangjieVersion::V3)?;
+    assert_eq!(*how_to_type, [CangjieCode::from_codes(b"rtw")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_sqlcipher_key_not_in_debug() {
+    let builder = LibCangjieHowToType::builder().key(KEY);
+    assert!(!format!("{builder:?}").contains(KEY));
+}
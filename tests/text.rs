@@ -0,0 +1,55 @@
+use std::error::Error;
+
+use libcangjie_howtotype::{CangjieCode, CangjieVersion, LibCangjieHowToType};
+
+#[test]
+fn test_how_to_type_text() -> Result<(), Box<dyn Error>> {
+    let cangjie = LibCangjieHowToType::new()?;
+
+    let annotated = cangjie
+        .how_to_type_text("喵屬😀", CangjieVersion::V5)
+        .collect::<Result<Vec<_>, _>>()?;
+    let segments = annotated
+        .iter()
+        .map(|(segment, _)| *segment)
+        .collect::<Vec<_>>();
+    assert_eq!(segments, ["喵", "屬", "😀"]);
+    assert_eq!(*annotated[0].1, [CangjieCode::from_radicals("口廿田")]);
+    assert_eq!(*annotated[1].1, [CangjieCode::from_radicals("尸水田戈")]);
+    assert!(annotated[2].1.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_how_to_type_text_variation_selectors() -> Result<(), Box<dyn Error>> {
+    let cangjie = LibCangjieHowToType::new()?;
+
+    let annotated = cangjie
+        .how_to_type_text("喵\u{E0100}屬\u{FE00}\u{FE01}", CangjieVersion::V3)
+        .collect::<Result<Vec<_>, _>>()?;
+    let segments = annotated
+        .iter()
+        .map(|(segment, _)| *segment)
+        .collect::<Vec<_>>();
+    assert_eq!(segments, ["喵\u{E0100}", "屬\u{FE00}\u{FE01}"]);
+    // Falls back to the base characters.
+    assert_eq!(*annotated[0].1, [CangjieCode::from_radicals("口廿田")]);
+    assert_eq!(*annotated[1].1, [CangjieCode::from_radicals("尸卜卜戈")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_how_to_type_text_empty() -> Result<(), Box<dyn Error>> {
+    let cangjie = LibCangjieHowToType::new()?;
+
+    assert!(
+        cangjie
+            .how_to_type_text("", CangjieVersion::V3)
+            .next()
+            .is_none()
+    );
+
+    Ok(())
+}
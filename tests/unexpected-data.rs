@@ -0,0 +1,76 @@
+mod common;
+
+use std::error::Error;
+
+use libcangjie_howtotype::{
+    CangjieCode, CangjieVersion, CodePattern, HowToTypeError, LibCangjieHowToType,
+};
+
+#[test]
+fn test_unexpected_value_type() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db(
+        "unexpected-value-type",
+        r"
+            INSERT INTO chars VALUES(1, '喵', '', 1, 1, 0, 0, 0, 0, 0, 0, 0, 500);
+            INSERT INTO codes VALUES(1, 3, X'727477', 0);
+        ",
+    )?;
+    let cangjie = LibCangjieHowToType::builder().path(&path).open()?;
+
+    let result = cangjie.how_to_type("喵", CangjieVersion::V3);
+    assert!(matches!(
+        result,
+        Err(HowToTypeError::UnexpectedValueType {
+            char_index: 1,
+            column: "codes.code",
+        }),
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_unexpected_character_type() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db(
+        "unexpected-character-type",
+        r"
+            INSERT INTO chars VALUES(7, X'E596B5FF', '', 1, 1, 0, 0, 0, 0, 0, 0, 0, 500);
+            INSERT INTO codes VALUES(7, 3, 'rtw', 0);
+        ",
+    )?;
+    let cangjie = LibCangjieHowToType::builder().path(&path).open()?;
+
+    let result = cangjie.characters_for(&CangjieCode::from_codes(b"rtw"), CangjieVersion::V3);
+    assert!(matches!(
+        result,
+        Err(HowToTypeError::UnexpectedValueType {
+            char_index: 7,
+            column: "chars.chchar",
+        }),
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_code() -> Result<(), Box<dyn Error>> {
+    let path = common::create_db(
+        "invalid-code",
+        r"
+            INSERT INTO chars VALUES(1, '喵', '', 1, 1, 0, 0, 0, 0, 0, 0, 0, 500);
+            INSERT INTO codes VALUES(1, 3, 'r1w', 0);
+        ",
+    )?;
+    let cangjie = LibCangjieHowToType::builder().path(&path).open()?;
+
+    let result = cangjie.how_to_type("喵", CangjieVersion::V3);
+    assert!(matches!(
+        result,
+        Err(HowToTypeError::InvalidCode { char_index: 1, ref raw }) if raw == "r1w",
+    ));
+
+    let result = cangjie.search(&CodePattern::from_codes(b"r*"), CangjieVersion::V3);
+    assert!(matches!(result, Err(HowToTypeError::InvalidCode { .. })));
+
+    Ok(())
+}
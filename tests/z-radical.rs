@@ -0,0 +1,31 @@
+use libcangjie_howtotype::{CangjieCode, CangjieRadical, CodePattern, ZRadical};
+
+#[test]
+fn test_parse_zhong() {
+    assert_eq!(CangjieRadical::from_radical('重'), CangjieRadical::Z);
+    assert_eq!(CangjieRadical::try_from('重'), Ok(CangjieRadical::Z));
+    assert_eq!(
+        CangjieCode::from_radicals("重難日十"),
+        CangjieCode::from_codes(b"zxaj"),
+    );
+    assert_eq!(
+        CangjieCode::try_from_radicals("重難日十"),
+        Ok(CangjieCode::from_codes(b"zxaj")),
+    );
+    assert_eq!("重*".parse(), Ok(CodePattern::from_codes(b"z*")));
+}
+
+#[test]
+fn test_display_z_radical() {
+    let code = CangjieCode::from_codes(b"zxaj");
+
+    assert_eq!(code.radicals().to_string(), "Ｚ難日十");
+    assert_eq!(
+        code.radicals().z_radical(ZRadical::FullWidthZ).to_string(),
+        "Ｚ難日十",
+    );
+    assert_eq!(
+        code.radicals().z_radical(ZRadical::Zhong).to_string(),
+        "重難日十",
+    );
+}